@@ -0,0 +1,193 @@
+//! Build script helpers for `axum-asset`.
+//!
+//! The `Asset` derive macro can only read files that exist when the crate is compiled. This crate lets a `build.rs`
+//! stage assets into `OUT_DIR` (optionally transforming them on the way) and emit a Rust module that embeds the staged
+//! directory, so generated front-end bundles can be embedded without checking them into the repository.
+//!
+//! # Example
+//!
+//! In `build.rs`:
+//!
+//! ```rust,no_run
+//! use axum_asset_build::AssetBuilder;
+//!
+//! fn main() -> std::io::Result<()> {
+//!     AssetBuilder::new("frontend/dist")
+//!         .transform(|path, contents| {
+//!             // Drop source maps from the embedded bundle
+//!             (path.extension().is_none_or(|ext| ext != "map")).then_some(contents)
+//!         })
+//!         .stage("dist")?
+//!         .emit_module("assets.rs", "FrontendAssets")?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! And in your crate:
+//!
+//! ```rust,ignore
+//! include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+//!
+//! let app: axum::Router = axum::Router::new().nest("/static", FrontendAssets::router());
+//! ```
+//!
+//! Alternatively, skip [`StagedAssets::emit_module`] and point the derive at the staged directory directly with
+//! `#[asset(dir = "$OUT_DIR/dist")]`.
+
+use std::{
+    env, fmt,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+};
+
+use walkdir::WalkDir;
+
+/// A transform applied to every file before it is staged.
+///
+/// Receives the path relative to the source directory and the file contents. Returning `None` skips the file.
+type Transform = Box<dyn Fn(&Path, Vec<u8>) -> Option<Vec<u8>>>;
+
+/// Stages a directory of assets into `OUT_DIR` from a build script.
+pub struct AssetBuilder {
+    source: PathBuf,
+    transforms: Vec<Transform>,
+}
+
+impl AssetBuilder {
+    /// Create a builder for the given source directory.
+    ///
+    /// Relative paths are resolved against the crate's `CARGO_MANIFEST_DIR`.
+    pub fn new(source: impl AsRef<Path>) -> Self {
+        let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+
+        Self {
+            source: PathBuf::from(manifest_dir).join(source),
+            transforms: Vec::new(),
+        }
+    }
+
+    /// Add a transform that runs on every file before it is staged.
+    ///
+    /// Transforms run in the order they were added. If any transform returns `None`, the file is not staged.
+    pub fn transform<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(&Path, Vec<u8>) -> Option<Vec<u8>> + 'static,
+    {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Copy all files into `$OUT_DIR/<name>`, applying transforms along the way.
+    ///
+    /// Any previous contents of the staging directory are removed first. The source directory is registered with
+    /// `cargo:rerun-if-changed` so the build script runs again when assets change. File modification times are
+    /// preserved so `Last-Modified` headers reflect the source files rather than the build time.
+    pub fn stage(self, name: &str) -> io::Result<StagedAssets> {
+        let out_dir = env::var("OUT_DIR").map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "`OUT_DIR` is not set; `AssetBuilder::stage` must be called from a build script",
+            )
+        })?;
+
+        self.stage_into(PathBuf::from(out_dir).join(name))
+    }
+
+    /// Same as [`AssetBuilder::stage`], but stages into `dir` instead of a directory under `OUT_DIR`.
+    ///
+    /// [`StagedAssets::emit_module`] writes the module next to `dir`, in its parent directory.
+    pub fn stage_into(self, dir: impl Into<PathBuf>) -> io::Result<StagedAssets> {
+        let dir = dir.into();
+
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        fs::create_dir_all(&dir)?;
+
+        println!("cargo:rerun-if-changed={}", self.source.display());
+
+        for entry in WalkDir::new(&self.source).follow_links(true) {
+            let entry = entry.map_err(io::Error::other)?;
+
+            // Skip directories
+            if entry.file_type().is_dir() {
+                continue;
+            }
+
+            let relative_path = entry
+                .path()
+                .strip_prefix(&self.source)
+                .map_err(io::Error::other)?;
+
+            let contents = self
+                .transforms
+                .iter()
+                .try_fold(fs::read(entry.path())?, |contents, transform| {
+                    transform(relative_path, contents)
+                });
+
+            let Some(contents) = contents else {
+                continue;
+            };
+
+            let target = dir.join(relative_path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&target, contents)?;
+
+            let modified = entry.metadata().map_err(io::Error::other)?.modified()?;
+            File::options()
+                .write(true)
+                .open(&target)?
+                .set_modified(modified)?;
+        }
+
+        Ok(StagedAssets { dir })
+    }
+}
+
+impl fmt::Debug for AssetBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssetBuilder")
+            .field("source", &self.source)
+            .field("transforms", &self.transforms.len())
+            .finish()
+    }
+}
+
+/// A directory of assets staged by [`AssetBuilder::stage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagedAssets {
+    dir: PathBuf,
+}
+
+impl StagedAssets {
+    /// Absolute path of the staging directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write `$OUT_DIR/<file_name>` containing a unit struct named `type_name` that derives `Asset` for the staged
+    /// directory.
+    ///
+    /// Include the module in your crate with `include!(concat!(env!("OUT_DIR"), "/<file_name>"))`.
+    pub fn emit_module(&self, file_name: &str, type_name: &str) -> io::Result<PathBuf> {
+        let out_dir = self.dir.parent().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "Staging directory has no parent")
+        })?;
+        let target = out_dir.join(file_name);
+
+        // `{:?}` produces a valid Rust string literal, including escaped backslashes on Windows. The derive expands
+        // `$VAR`, so a `$` in the path is escaped as `$$`
+        let module = format!(
+            "#[derive(::axum_asset::Asset)]\n#[asset(dir = {:?})]\npub struct {type_name};\n",
+            self.dir.to_string_lossy().replace('$', "$$"),
+        );
+        fs::write(&target, module)?;
+
+        Ok(target)
+    }
+}
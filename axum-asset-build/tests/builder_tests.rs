@@ -0,0 +1,167 @@
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use axum_asset_build::AssetBuilder;
+
+const MTIME: u64 = 1_704_164_646;
+
+/// Create a source directory unique to the test, with a few files.
+fn source_dir(name: &str) -> PathBuf {
+    let dir = scratch_dir(name).join("src");
+
+    fs::create_dir_all(dir.join("js")).unwrap();
+    fs::write(dir.join("index.html"), "<p>__VERSION__</p>").unwrap();
+    fs::write(dir.join("js/app.js"), "console.log('__VERSION__');").unwrap();
+    fs::write(dir.join("js/app.js.map"), "{}").unwrap();
+
+    File::options()
+        .write(true)
+        .open(dir.join("index.html"))
+        .unwrap()
+        .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(MTIME))
+        .unwrap();
+
+    dir
+}
+
+/// Create an empty directory unique to the test.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+        .join("asset-builder")
+        .join(name);
+
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
+
+/// List the files under `dir` as sorted relative paths.
+fn files(dir: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(current).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                let relative_path = path.strip_prefix(dir).unwrap();
+                files.push(relative_path.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+#[test]
+fn test_stage() {
+    let source = source_dir("stage");
+    let target = scratch_dir("stage-out").join("dist");
+
+    // Leftovers from a previous build are removed
+    fs::create_dir_all(&target).unwrap();
+    fs::write(target.join("stale.txt"), "stale").unwrap();
+
+    let staged = AssetBuilder::new(&source).stage_into(&target).unwrap();
+
+    assert_eq!(staged.dir(), target);
+    assert_eq!(files(&target), ["index.html", "js/app.js", "js/app.js.map"]);
+    assert_eq!(
+        fs::read(target.join("js/app.js")).unwrap(),
+        fs::read(source.join("js/app.js")).unwrap()
+    );
+    assert_eq!(
+        fs::metadata(target.join("index.html"))
+            .unwrap()
+            .modified()
+            .unwrap(),
+        SystemTime::UNIX_EPOCH + Duration::from_secs(MTIME)
+    );
+}
+
+#[test]
+fn test_transforms() {
+    let source = source_dir("transforms");
+    let target = scratch_dir("transforms-out").join("dist");
+
+    let staged = AssetBuilder::new(&source)
+        .transform(|path, contents| {
+            (path.extension().is_none_or(|ext| ext != "map")).then_some(contents)
+        })
+        .transform(|_, contents| {
+            Some(
+                String::from_utf8(contents)
+                    .unwrap()
+                    .replace("__VERSION__", "1.2.3")
+                    .into_bytes(),
+            )
+        })
+        .transform(|path, contents| {
+            // Runs after the previous transforms, with paths relative to the source directory
+            assert!(path.is_relative());
+            assert!(!String::from_utf8_lossy(&contents).contains("__VERSION__"));
+            Some(contents)
+        })
+        .stage_into(&target)
+        .unwrap();
+
+    assert_eq!(files(staged.dir()), ["index.html", "js/app.js"]);
+    assert_eq!(
+        fs::read_to_string(target.join("js/app.js")).unwrap(),
+        "console.log('1.2.3');"
+    );
+
+    // The source directory is left untouched
+    assert_eq!(
+        fs::read_to_string(source.join("index.html")).unwrap(),
+        "<p>__VERSION__</p>"
+    );
+}
+
+#[test]
+fn test_emit_module() {
+    let source = source_dir("emit");
+    let target = scratch_dir("emit-out").join("dist$1");
+
+    let staged = AssetBuilder::new(&source).stage_into(&target).unwrap();
+    let module = staged.emit_module("assets.rs", "SiteAssets").unwrap();
+
+    assert_eq!(module, target.parent().unwrap().join("assets.rs"));
+
+    // `$` is escaped so the derive does not expand it as an environment variable
+    let dir = format!("{:?}", target.to_string_lossy().replace('$', "$$"));
+    assert!(dir.contains("dist$$1"));
+    assert_eq!(
+        fs::read_to_string(module).unwrap(),
+        format!("#[derive(::axum_asset::Asset)]\n#[asset(dir = {dir})]\npub struct SiteAssets;\n")
+    );
+}
+
+#[test]
+fn test_stage_outside_build_script() {
+    let source = source_dir("outside");
+
+    // Integration tests of a crate without a build script have no `OUT_DIR`
+    let error = AssetBuilder::new(&source).stage("dist").unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_missing_source() {
+    let dir = scratch_dir("missing");
+
+    assert!(
+        AssetBuilder::new(dir.join("nope"))
+            .stage_into(dir.join("dist"))
+            .is_err()
+    );
+}
@@ -0,0 +1,205 @@
+//! Reading zip and tar archives into memory.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Seek},
+    path::{Component, Path},
+};
+
+use flate2::read::GzDecoder;
+use zip::{ExtraField, ZipArchive};
+
+/// Largest capacity reserved up front for an entry, whatever size its header claims.
+///
+/// Sizes come from the archive itself, so a crafted header could otherwise force a huge allocation before any data is
+/// read. Larger entries still load; their buffer just grows as the data arrives.
+const MAX_INITIAL_CAPACITY: u64 = 1024 * 1024;
+
+/// A file read from an archive.
+#[derive(Debug)]
+pub struct ArchiveEntry {
+    /// Path of the entry inside the archive (with forward slashes).
+    pub path: String,
+
+    /// Entry contents.
+    pub contents: Vec<u8>,
+
+    /// Unix timestamp of last modification.
+    pub last_modified: u64,
+}
+
+/// Archive formats, chosen from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// `.zip`
+    Zip,
+
+    /// `.tar`
+    Tar,
+
+    /// `.tar.gz` or `.tgz`
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Detect the format of an archive from its file name.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_string_lossy().to_lowercase();
+
+        if file_name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if file_name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// Read all file entries of an archive on disk, detecting the format with [`ArchiveFormat::from_path`].
+pub fn read_path(path: &Path) -> io::Result<Vec<ArchiveEntry>> {
+    let Some(format) = ArchiveFormat::from_path(path) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "Unsupported archive format: {}, expected `.zip`, `.tar`, `.tar.gz` or `.tgz`",
+                path.display()
+            ),
+        ));
+    };
+    let file = BufReader::new(File::open(path)?);
+
+    match format {
+        ArchiveFormat::Zip => read_zip(file),
+        ArchiveFormat::Tar => read_tar(file),
+        ArchiveFormat::TarGz => read_tar_gz(file),
+    }
+}
+
+/// Read all file entries of a zip archive.
+///
+/// Directory entries and entries whose paths are absolute or contain `..` are skipped.
+pub fn read_zip(reader: impl Read + Seek) -> io::Result<Vec<ArchiveEntry>> {
+    let mut archive = ZipArchive::new(reader).map_err(io::Error::other)?;
+    let mut entries = Vec::new();
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(io::Error::other)?;
+
+        let Some(path) = entry.enclosed_name().and_then(|path| entry_path(&path)) else {
+            continue;
+        };
+        if entry.is_dir() {
+            continue;
+        }
+
+        // Prefer the extended timestamp, which is UTC; the DOS timestamp has no time zone
+        let extended_timestamp = entry.extra_data_fields().find_map(|field| match field {
+            ExtraField::ExtendedTimestamp(timestamp) => timestamp.mod_time(),
+            _ => None,
+        });
+        let last_modified = match extended_timestamp {
+            Some(timestamp) => u64::from(timestamp),
+            None => entry
+                .last_modified()
+                .map(|time| {
+                    unix_timestamp(
+                        time.year(),
+                        time.month(),
+                        time.day(),
+                        time.hour(),
+                        time.minute(),
+                        time.second(),
+                    )
+                })
+                .unwrap_or(0),
+        };
+
+        let mut contents = Vec::with_capacity(entry.size().min(MAX_INITIAL_CAPACITY) as usize);
+        entry.read_to_end(&mut contents)?;
+
+        entries.push(ArchiveEntry {
+            path,
+            contents,
+            last_modified,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Read all file entries of an uncompressed tar archive.
+///
+/// Entries that are not regular files and entries whose paths are absolute or contain `..` are skipped.
+pub fn read_tar(reader: impl Read) -> io::Result<Vec<ArchiveEntry>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let Some(path) = entry_path(&entry.path()?) else {
+            continue;
+        };
+        let last_modified = entry.header().mtime().unwrap_or(0);
+
+        let mut contents = Vec::with_capacity(entry.size().min(MAX_INITIAL_CAPACITY) as usize);
+        entry.read_to_end(&mut contents)?;
+
+        entries.push(ArchiveEntry {
+            path,
+            contents,
+            last_modified,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Read all file entries of a gzip-compressed tar archive.
+pub fn read_tar_gz(reader: impl Read) -> io::Result<Vec<ArchiveEntry>> {
+    read_tar(GzDecoder::new(reader))
+}
+
+/// Normalize an entry path to forward slashes, dropping `./` components.
+///
+/// Returns `None` for paths that are absolute or contain `..`.
+fn entry_path(path: &Path) -> Option<String> {
+    let mut segments = Vec::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(segment) => segments.push(segment.to_str()?),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+
+    (!segments.is_empty()).then(|| segments.join("/"))
+}
+
+/// Convert a zip DOS date and time to a Unix timestamp.
+///
+/// DOS timestamps are local time of the machine that created the archive, with no time zone recorded. They are
+/// interpreted as UTC, so the result may be off by the creator's UTC offset.
+fn unix_timestamp(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> u64 {
+    // Days since 1970-01-01, from Howard Hinnant's `days_from_civil`
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    let seconds =
+        days * 86_400 + i64::from(hour) * 3_600 + i64::from(minute) * 60 + i64::from(second);
+
+    u64::try_from(seconds).unwrap_or(0)
+}
@@ -0,0 +1,11 @@
+//! Internal helpers shared by `axum-asset` and `axum-asset-derive`.
+//!
+//! Files are embedded at compile time by the derive macro and loaded at runtime by sources such as `FsOverride` and
+//! `ArchiveSource`. Both paths must agree on details like charsets and archive entry paths, so that logic lives here
+//! once. This crate is not a public API and may change in any release.
+
+#[cfg(feature = "archive")]
+pub mod archive;
+mod mime;
+
+pub use self::mime::is_text_mime_type;
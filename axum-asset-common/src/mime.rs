@@ -0,0 +1,16 @@
+/// Check if a MIME type describes text that should be served with a charset.
+///
+/// This covers `text/*`, JavaScript, JSON and XML, including `+json` and `+xml` variants.
+pub fn is_text_mime_type(mime_type: &str) -> bool {
+    let Ok(mime) = mime_type.parse::<mime_guess::Mime>() else {
+        return false;
+    };
+
+    let subtype = mime.subtype().as_str();
+    let suffix = mime.suffix().map(|suffix| suffix.as_str());
+
+    mime.type_() == mime_guess::mime::TEXT
+        || matches!(suffix, Some("json" | "xml"))
+        || (mime.type_() == mime_guess::mime::APPLICATION
+            && matches!(subtype, "javascript" | "ecmascript" | "json" | "xml"))
+}
@@ -0,0 +1,26 @@
+use std::path::Path;
+
+use axum_asset_common::archive::{self, ArchiveEntry, ArchiveFormat};
+use proc_macro2::Span;
+
+/// Read all file entries from a zip, tar or gzip-compressed tar archive.
+///
+/// The format is chosen from the extension: `.zip`, `.tar`, or `.tar.gz`/`.tgz`.
+pub fn read_entries(span: Span, path: &Path) -> Result<Vec<ArchiveEntry>, syn::Error> {
+    if ArchiveFormat::from_path(path).is_none() {
+        return Err(syn::Error::new(
+            span,
+            format!(
+                "Unsupported archive format: {}, expected `.zip`, `.tar`, `.tar.gz` or `.tgz`",
+                path.display()
+            ),
+        ));
+    }
+
+    archive::read_path(path).map_err(|e| {
+        syn::Error::new(
+            span,
+            format!("Failed to read archive {}: {}", path.display(), e),
+        )
+    })
+}
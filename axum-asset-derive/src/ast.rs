@@ -1,95 +1,419 @@
 use syn::{
-    Attribute, DeriveInput, Expr, Generics, Ident, Lit, LitStr, Meta, MetaNameValue,
-    spanned::Spanned,
+    Attribute, DeriveInput, Generics, Ident, LitBool, LitInt, LitStr, Path, Token,
+    meta::ParseNestedMeta, parse::Parse, spanned::Spanned,
 };
 
+/// Keys accepted in `#[asset(...)]`.
+const KEYS: &[&str] = &[
+    "dir",
+    "archive",
+    "prefix",
+    "strip",
+    "mime",
+    "sniff",
+    "strip_bom",
+    "normalize_newlines",
+    "integrity",
+    "etag_hash",
+    "dedup_note",
+    "report",
+    "max_file_size",
+    "max_total_size",
+];
+
+/// Keys accepted in `mime(...)`.
+const MIME_KEYS: &[&str] = &["ext", "path", "type"];
+
 pub struct AssetAst {
-    pub dir: LitStr,
+    pub sources: Vec<Source>,
+    pub mime_overrides: Vec<MimeOverride>,
+    pub sniff: bool,
+    pub strip_bom: bool,
+    pub normalize_newlines: bool,
+    pub integrity: Vec<IntegrityAlgorithm>,
+    pub etag_hash: EtagHash,
+    pub dedup_note: bool,
+    pub report: Option<LitStr>,
+    pub max_file_size: Option<LitInt>,
+    pub max_total_size: Option<LitInt>,
     pub ident: Ident,
     pub generics: Generics,
 }
 
+/// A source of assets from the `dir` or `archive` key of an `#[asset]` attribute.
+pub struct Source {
+    /// Where the files are read from.
+    pub location: SourceLocation,
+
+    /// Path prefix prepended to every file from this source.
+    pub prefix: Option<LitStr>,
+
+    /// Leading directory removed from the paths of files under it.
+    pub strip: Option<LitStr>,
+}
+
+/// Location of the files of a [`Source`].
+pub enum SourceLocation {
+    /// A directory, from the `dir` key.
+    Dir(LitStr),
+
+    /// A zip or tar archive, from the `archive` key.
+    Archive(LitStr),
+}
+
+impl SourceLocation {
+    /// The path as written in the attribute.
+    pub fn lit(&self) -> &LitStr {
+        match self {
+            SourceLocation::Dir(lit) | SourceLocation::Archive(lit) => lit,
+        }
+    }
+}
+
+/// A MIME type override from a `mime(...)` entry in the `#[asset]` attribute.
+pub struct MimeOverride {
+    /// Files the override applies to.
+    pub target: MimeTarget,
+
+    /// MIME type to use for matching files.
+    pub mime_type: LitStr,
+}
+
+/// Files matched by a [`MimeOverride`].
+pub enum MimeTarget {
+    /// All files with the given extension (without the leading dot).
+    Extension(LitStr),
+
+    /// A single file at the given path, as served (including any prefix).
+    Path(LitStr),
+}
+
+/// Hash algorithm used for Subresource Integrity metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrityAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl IntegrityAlgorithm {
+    fn parse_list(lit_str: &LitStr) -> Result<Vec<Self>, syn::Error> {
+        let mut algorithms = Vec::new();
+
+        for name in lit_str.value().split_whitespace() {
+            let algorithm = match name {
+                "sha256" => Self::Sha256,
+                "sha384" => Self::Sha384,
+                "sha512" => Self::Sha512,
+                _ => {
+                    return Err(syn::Error::new(
+                        lit_str.span(),
+                        format!(
+                            "Unsupported integrity algorithm `{name}`, expected `sha256`, `sha384` or `sha512`"
+                        ),
+                    ));
+                }
+            };
+
+            if !algorithms.contains(&algorithm) {
+                algorithms.push(algorithm);
+            }
+        }
+
+        if algorithms.is_empty() {
+            return Err(syn::Error::new(
+                lit_str.span(),
+                "Expected at least one integrity algorithm",
+            ));
+        }
+
+        Ok(algorithms)
+    }
+}
+
+/// Hash algorithm used to compute the `ETag` value of each file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EtagHash {
+    /// Reuse the SHA-256 content hash.
+    #[default]
+    Sha256,
+    Blake3,
+    Xxh3,
+}
+
+impl EtagHash {
+    fn parse(lit_str: &LitStr) -> Result<Self, syn::Error> {
+        match lit_str.value().as_str() {
+            "sha256" => Ok(Self::Sha256),
+            "blake3" => Ok(Self::Blake3),
+            "xxh3" => Ok(Self::Xxh3),
+            other => Err(syn::Error::new(
+                lit_str.span(),
+                format!("Unsupported ETag hash `{other}`, expected `sha256`, `blake3` or `xxh3`"),
+            )),
+        }
+    }
+}
+
 impl TryFrom<DeriveInput> for AssetAst {
     type Error = syn::Error;
 
     fn try_from(value: DeriveInput) -> Result<Self, Self::Error> {
         let asset_attributes = collect_asset_attributes(&value);
 
-        if asset_attributes.is_empty() {
+        let Some(first_attribute) = asset_attributes.first() else {
             return Err(syn::Error::new(
                 value.ident.span(),
                 "Expected `#[asset(dir = \"path/to/dir\")]` attribute",
             ));
-        }
-
-        if asset_attributes.len() > 1 {
-            return Err(syn::Error::new(
-                value.ident.span(),
-                "Expected only one `#[asset]` attribute",
-            ));
-        }
+        };
 
-        let asset_attribute = asset_attributes.into_iter().next().unwrap();
+        let mut attributes = AssetAttributes::default();
+        let mut sources = Vec::new();
 
-        let dir = match asset_attribute.meta {
-            Meta::List(meta_list) => {
-                let meta_name_value: MetaNameValue = syn::parse2(meta_list.tokens)?;
+        // Each attribute may declare one source directory or archive; all other keys apply to the whole type
+        for asset_attribute in &asset_attributes {
+            let mut source = SourceAttributes::default();
+            asset_attribute.parse_nested_meta(|meta| attributes.parse_meta(meta, &mut source))?;
 
-                if !meta_name_value.path.is_ident("dir") {
+            let location = match (source.dir, source.archive) {
+                (Some(_), Some(archive)) => {
                     return Err(syn::Error::new(
-                        value.ident.span(),
-                        "Expected only one `#[asset]` attribute",
+                        archive.span(),
+                        "`dir` and `archive` cannot be used in the same `#[asset]` attribute",
                     ));
                 }
+                (Some(dir), None) => Some(SourceLocation::Dir(dir)),
+                (None, Some(archive)) => Some(SourceLocation::Archive(archive)),
+                (None, None) => None,
+            };
 
-                meta_name_value.value
-            }
-            Meta::NameValue(_) => {
-                return Err(syn::Error::new(
-                    value.ident.span(),
-                    "Expected only one `#[asset]` attribute",
-                ));
-            }
-            Meta::Path(_) => {
-                return Err(syn::Error::new(
-                    value.ident.span(),
-                    "Expected only one `#[asset]` attribute",
-                ));
+            match location {
+                Some(location) => sources.push(Source {
+                    location,
+                    prefix: source.prefix,
+                    strip: source.strip,
+                }),
+                None => {
+                    let orphan = [("prefix", source.prefix), ("strip", source.strip)]
+                        .into_iter()
+                        .find_map(|(key, lit)| Some((key, lit?)));
+
+                    if let Some((key, lit)) = orphan {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            format!(
+                                "`{key}` requires `dir` or `archive` in the same `#[asset]` attribute"
+                            ),
+                        ));
+                    }
+                }
             }
-        };
+        }
+
+        if sources.is_empty() {
+            return Err(syn::Error::new(
+                first_attribute.path().span(),
+                "Missing required key `dir` or `archive`, expected `#[asset(dir = \"path/to/dir\")]`",
+            ));
+        }
 
         Ok(Self {
-            dir: get_dir_from_expr(dir)?,
+            sources,
+            mime_overrides: attributes.mime_overrides,
+            sniff: flag(attributes.sniff),
+            strip_bom: flag(attributes.strip_bom),
+            normalize_newlines: flag(attributes.normalize_newlines),
+            integrity: attributes
+                .integrity
+                .unwrap_or_else(|| vec![IntegrityAlgorithm::Sha384]),
+            etag_hash: attributes.etag_hash.unwrap_or_default(),
+            dedup_note: flag(attributes.dedup_note),
+            report: attributes.report,
+            max_file_size: attributes.max_file_size,
+            max_total_size: attributes.max_total_size,
             ident: value.ident,
             generics: value.generics,
         })
     }
 }
 
-fn collect_asset_attributes(input: &DeriveInput) -> Vec<Attribute> {
-    let mut attrs = Vec::new();
+/// Keys describing a source directory, parsed separately for each `#[asset(...)]` attribute.
+#[derive(Default)]
+struct SourceAttributes {
+    dir: Option<LitStr>,
+    archive: Option<LitStr>,
+    prefix: Option<LitStr>,
+    strip: Option<LitStr>,
+}
+
+/// Keys parsed from all `#[asset(...)]` attributes, each set at most once.
+#[derive(Default)]
+struct AssetAttributes {
+    mime_overrides: Vec<MimeOverride>,
+    sniff: Option<LitBool>,
+    strip_bom: Option<LitBool>,
+    normalize_newlines: Option<LitBool>,
+    integrity: Option<Vec<IntegrityAlgorithm>>,
+    etag_hash: Option<EtagHash>,
+    dedup_note: Option<LitBool>,
+    report: Option<LitStr>,
+    max_file_size: Option<LitInt>,
+    max_total_size: Option<LitInt>,
+}
 
-    for attr in input.attrs.iter() {
-        if attr.path().is_ident("asset") {
-            attrs.push(attr.clone());
+impl AssetAttributes {
+    fn parse_meta(
+        &mut self,
+        meta: ParseNestedMeta<'_>,
+        source: &mut SourceAttributes,
+    ) -> Result<(), syn::Error> {
+        let key = key_name(&meta.path);
+
+        match key.as_str() {
+            "dir" => set_once(&mut source.dir, &meta, value(&meta, "a string literal")?),
+            "archive" => set_once(
+                &mut source.archive,
+                &meta,
+                value(&meta, "a string literal")?,
+            ),
+            "prefix" => set_once(&mut source.prefix, &meta, value(&meta, "a string literal")?),
+            "strip" => set_once(&mut source.strip, &meta, value(&meta, "a string literal")?),
+            "mime" => {
+                let mime_override = parse_mime_override(&meta)?;
+                self.mime_overrides.push(mime_override);
+                Ok(())
+            }
+            "sniff" => set_once(&mut self.sniff, &meta, bool_value(&meta)?),
+            "strip_bom" => set_once(&mut self.strip_bom, &meta, bool_value(&meta)?),
+            "normalize_newlines" => {
+                set_once(&mut self.normalize_newlines, &meta, bool_value(&meta)?)
+            }
+            "integrity" => {
+                let algorithms =
+                    IntegrityAlgorithm::parse_list(&value(&meta, "a string literal")?)?;
+                set_once(&mut self.integrity, &meta, algorithms)
+            }
+            "etag_hash" => {
+                let etag_hash = EtagHash::parse(&value(&meta, "a string literal")?)?;
+                set_once(&mut self.etag_hash, &meta, etag_hash)
+            }
+            "dedup_note" => set_once(&mut self.dedup_note, &meta, bool_value(&meta)?),
+            "report" => set_once(&mut self.report, &meta, value(&meta, "a string literal")?),
+            "max_file_size" => set_once(&mut self.max_file_size, &meta, int_value(&meta)?),
+            "max_total_size" => set_once(&mut self.max_total_size, &meta, int_value(&meta)?),
+            _ => Err(unknown_key(&meta, &key, KEYS)),
         }
     }
+}
 
-    attrs
+fn collect_asset_attributes(input: &DeriveInput) -> Vec<&Attribute> {
+    input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("asset"))
+        .collect()
 }
 
-fn get_dir_from_expr(expr: Expr) -> Result<LitStr, syn::Error> {
-    match expr {
-        Expr::Lit(lit) => match lit.lit {
-            Lit::Str(lit_str) => Ok(lit_str),
-            _ => Err(syn::Error::new(
-                lit.span(),
-                "Expected a literal string for the `dir` attribute",
-            )),
-        },
-        _ => Err(syn::Error::new(
-            expr.span(),
-            "Expected a literal string for the `dir` attribute",
-        )),
+fn parse_mime_override(meta: &ParseNestedMeta<'_>) -> Result<MimeOverride, syn::Error> {
+    let mut target = None;
+    let mut mime_type: Option<LitStr> = None;
+
+    meta.parse_nested_meta(|nested| {
+        let key = key_name(&nested.path);
+
+        match key.as_str() {
+            "ext" => set_once(
+                &mut target,
+                &nested,
+                MimeTarget::Extension(value(&nested, "a string literal")?),
+            ),
+            "path" => set_once(
+                &mut target,
+                &nested,
+                MimeTarget::Path(value(&nested, "a string literal")?),
+            ),
+            "type" => set_once(&mut mime_type, &nested, value(&nested, "a string literal")?),
+            _ => Err(unknown_key(&nested, &key, MIME_KEYS)),
+        }
+    })?;
+
+    let (Some(target), Some(mime_type)) = (target, mime_type) else {
+        return Err(meta.error(
+            "Expected `mime(ext = \"...\", type = \"...\")` or `mime(path = \"...\", type = \"...\")`",
+        ));
+    };
+
+    if mime_type.value().parse::<mime_guess::Mime>().is_err() {
+        return Err(syn::Error::new(
+            mime_type.span(),
+            format!("Invalid MIME type: {}", mime_type.value()),
+        ));
+    }
+
+    Ok(MimeOverride { target, mime_type })
+}
+
+/// Name of a key, including keywords such as `type`.
+fn key_name(path: &Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Store a value, rejecting keys that appear more than once.
+fn set_once<T>(
+    slot: &mut Option<T>,
+    meta: &ParseNestedMeta<'_>,
+    value: T,
+) -> Result<(), syn::Error> {
+    if slot.is_some() {
+        return Err(meta.error(format!("Duplicate key `{}`", key_name(&meta.path))));
+    }
+
+    *slot = Some(value);
+    Ok(())
+}
+
+fn unknown_key(meta: &ParseNestedMeta<'_>, key: &str, keys: &[&str]) -> syn::Error {
+    let keys = keys
+        .iter()
+        .map(|key| format!("`{key}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    meta.error(format!("Unknown key `{key}`, expected one of: {keys}"))
+}
+
+/// Parse the `= value` part of a key, reporting `expected` at the value on failure.
+fn value<T: Parse>(meta: &ParseNestedMeta<'_>, expected: &str) -> Result<T, syn::Error> {
+    let key = key_name(&meta.path);
+    let input = meta.value()?;
+
+    input
+        .parse()
+        .map_err(|e| syn::Error::new(e.span(), format!("Expected {expected} for `{key}`")))
+}
+
+/// Parse a boolean key, where the bare key (without `= value`) means `true`.
+fn bool_value(meta: &ParseNestedMeta<'_>) -> Result<LitBool, syn::Error> {
+    if meta.input.peek(Token![=]) {
+        value(meta, "`true` or `false`")
+    } else {
+        Ok(LitBool::new(true, meta.path.span()))
     }
 }
+
+fn int_value(meta: &ParseNestedMeta<'_>) -> Result<LitInt, syn::Error> {
+    let lit_int: LitInt = value(meta, "an integer literal")?;
+
+    // Validate eagerly so later uses can't fail
+    lit_int.base10_parse::<u64>()?;
+    Ok(lit_int)
+}
+
+fn flag(value: Option<LitBool>) -> bool {
+    value.is_some_and(|value| value.value)
+}
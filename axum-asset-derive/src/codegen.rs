@@ -1,41 +1,450 @@
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
 
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
+use syn::{Ident, LitStr};
 
-use crate::{ast::AssetAst, file::FileInfo};
+use crate::{
+    ast::{AssetAst, MimeTarget, SourceLocation},
+    file::{FileInfo, LoadOptions, PathMapping},
+    report::Totals,
+};
 
 pub fn impl_derive_asset(ast: AssetAst) -> Result<TokenStream, syn::Error> {
-    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
-    let base_dir = PathBuf::from(&manifest_dir).join(ast.dir.value());
+    let load_options = LoadOptions {
+        mime_overrides: &ast.mime_overrides,
+        sniff: ast.sniff,
+        strip_bom: ast.strip_bom,
+        normalize_newlines: ast.normalize_newlines,
+        integrity: &ast.integrity,
+        etag_hash: ast.etag_hash,
+    };
 
-    let files = crate::file::collect_files(ast.dir.span(), &base_dir)?;
+    let mut files = Vec::new();
+    let mut warnings = Vec::new();
+    let mut sources_by_path: HashMap<String, &LitStr> = HashMap::new();
 
-    let ident = ast.ident;
-    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    for source in &ast.sources {
+        let source_lit = source.location.lit();
+        let source_path = resolve_path(source_lit)?;
+        let prefix = source
+            .prefix
+            .as_ref()
+            .map(LitStr::value)
+            .unwrap_or_default();
+        let strip = source.strip.as_ref().map(LitStr::value).unwrap_or_default();
+        let mapping = PathMapping {
+            strip: strip.trim_matches('/'),
+            prefix: prefix.trim_matches('/'),
+        };
+
+        let (source_files, strip_exists) = match &source.location {
+            SourceLocation::Dir(_) => {
+                let strip_exists = source_path.join(mapping.strip).is_dir();
+                let files = crate::file::collect_files(
+                    source_lit.span(),
+                    &source_path,
+                    &mapping,
+                    &load_options,
+                )?;
+
+                (files, strip_exists)
+            }
+            SourceLocation::Archive(_) => {
+                let entries = crate::archive::read_entries(source_lit.span(), &source_path)?;
+                let strip_exists = entries.iter().any(|entry| {
+                    entry
+                        .path
+                        .strip_prefix(mapping.strip)
+                        .is_some_and(|rest| rest.starts_with('/'))
+                });
+                let files = entries
+                    .into_iter()
+                    .map(|entry| {
+                        FileInfo::new(
+                            mapping.apply(entry.path),
+                            entry.contents,
+                            entry.last_modified,
+                            &load_options,
+                        )
+                    })
+                    .collect();
+
+                (files, strip_exists)
+            }
+        };
+
+        // A `strip` that names no directory would silently do nothing
+        if let Some(strip_lit) = &source.strip
+            && !strip_exists
+        {
+            return Err(syn::Error::new(
+                strip_lit.span(),
+                format!(
+                    "`{}` is not a directory in `{}`",
+                    strip_lit.value(),
+                    source_lit.value()
+                ),
+            ));
+        }
+
+        for file in source_files {
+            // Merged sources must not provide the same path twice
+            if let Some(other_lit) = sources_by_path.insert(file.relative_path.clone(), source_lit)
+            {
+                let mut error = syn::Error::new(
+                    source_lit.span(),
+                    format!(
+                        "Duplicate asset path `{}` from `{}` and `{}`",
+                        file.relative_path,
+                        other_lit.value(),
+                        source_lit.value()
+                    ),
+                );
+                error.combine(syn::Error::new(
+                    other_lit.span(),
+                    format!("`{}` is also provided here", file.relative_path),
+                ));
+
+                return Err(error);
+            }
+
+            warnings.extend(
+                file.warnings
+                    .iter()
+                    .map(|message| (source_lit.span(), message.clone())),
+            );
+            files.push(file);
+        }
+    }
+
+    // Sort by path for deterministic output
+    files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+    // A path can't be both a file and a directory, whether the clash comes from merged sources or `strip`/`prefix`
+    for file in &files {
+        let dirs = file
+            .relative_path
+            .match_indices('/')
+            .map(|(index, _)| &file.relative_path[..index]);
+
+        for dir in dirs {
+            if let Some(other_lit) = sources_by_path.get(dir) {
+                let source_lit = sources_by_path[&file.relative_path];
+                let mut error = syn::Error::new(
+                    source_lit.span(),
+                    format!(
+                        "Asset path `{dir}` is a file from `{}` and a directory containing `{}` from `{}`",
+                        other_lit.value(),
+                        file.relative_path,
+                        source_lit.value()
+                    ),
+                );
+                error.combine(syn::Error::new(
+                    other_lit.span(),
+                    format!("`{dir}` is provided as a file here"),
+                ));
+
+                return Err(error);
+            }
+        }
+    }
+
+    // Path overrides that match nothing are almost certainly typos
+    for mime_override in &ast.mime_overrides {
+        if let MimeTarget::Path(path) = &mime_override.target
+            && !files.iter().any(|file| file.relative_path == path.value())
+        {
+            return Err(syn::Error::new(
+                path.span(),
+                format!(
+                    "No embedded file matches MIME override path: {}",
+                    path.value()
+                ),
+            ));
+        }
+    }
 
     let len = files.len();
 
+    let deduplicated = deduplicate(&files);
+
+    let size = files.iter().map(|file| file.contents.len()).sum::<usize>();
+    let totals = Totals {
+        files: len,
+        size,
+        embedded_size: size - deduplicated.saved_bytes,
+        saved_bytes: deduplicated.saved_bytes,
+    };
+
+    if let Some(report) = &ast.report {
+        let report_path = resolve_path(report)?;
+
+        crate::report::write_report(&report_path, &files, &totals).map_err(|e| {
+            syn::Error::new(
+                report.span(),
+                format!("Failed to write report {}: {}", report_path.display(), e),
+            )
+        })?;
+    }
+
+    check_budgets(&ast, &files, &totals)?;
+
+    let files_static = files_static(&files, &deduplicated);
     let get_expr = get_expr(&files);
-    let iter_expr = iter_expr(&files);
+    let read_dir_expr = read_dir_expr(&files);
+    let total_size = size as u64;
+
+    let dedup_note = (ast.dedup_note && deduplicated.duplicate_files > 0).then(|| {
+        format!(
+            "Deduplicated {} embedded file(s) with identical contents, saving {} bytes",
+            deduplicated.duplicate_files, deduplicated.saved_bytes
+        )
+    });
+
+    if let Some(dedup_note) = dedup_note {
+        warnings.push((ast.sources[0].location.lit().span(), dedup_note));
+    }
 
+    let warnings = warnings
+        .iter()
+        .map(|(span, message)| crate::warning::warning(*span, message));
+
+    let ident = ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    // The statics are shared by every method, so keep them in an anonymous const next to the impl
     Ok(quote! {
-        impl #impl_generics ::axum_asset::Asset for #ident #ty_generics #where_clause {
-            fn get(path: &str) -> ::core::option::Option<::axum_asset::EmbeddedFile> {
-                #get_expr
+        #(#warnings)*
+
+        const _: () = {
+            #files_static
+
+            impl #impl_generics ::axum_asset::Asset for #ident #ty_generics #where_clause {
+                fn get(path: &str) -> ::core::option::Option<::axum_asset::EmbeddedFile> {
+                    #get_expr
+                }
+
+                fn iter() -> impl ::core::iter::Iterator<Item = &'static str> {
+                    FILES.iter().map(|file| file.path)
+                }
+
+                fn files() -> impl ::core::iter::Iterator<Item = ::axum_asset::EmbeddedFile> {
+                    FILES.iter().copied()
+                }
+
+                fn len() -> usize {
+                    #len
+                }
+
+                fn total_size() -> u64 {
+                    #total_size
+                }
+
+                fn read_dir(path: &str) -> impl ::core::iter::Iterator<Item = ::axum_asset::DirEntry> {
+                    #read_dir_expr
+                }
+
+                fn walk(prefix: &str) -> impl ::core::iter::Iterator<Item = ::axum_asset::EmbeddedFile> {
+                    let prefix = prefix.trim_matches('/');
+                    let dir = if prefix.is_empty() {
+                        ::std::string::String::new()
+                    } else {
+                        ::std::format!("{prefix}/")
+                    };
+                    let start = FILES.partition_point(|file| file.path < dir.as_str());
+
+                    FILES[start..]
+                        .iter()
+                        .take_while(move |file| file.path.starts_with(dir.as_str()))
+                        .copied()
+                }
             }
+        };
+    })
+}
+
+/// Resolve a path attribute (such as `dir`) to an absolute path.
+///
+/// Environment variables (`$VAR` or `${VAR}`) are expanded first, so directories generated by a build script can be
+/// referenced with `$OUT_DIR`; `$$` stands for a literal `$`. Relative paths are resolved against the crate's
+/// `CARGO_MANIFEST_DIR`.
+fn resolve_path(path: &LitStr) -> Result<PathBuf, syn::Error> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let expanded =
+        expand_env(&path.value()).map_err(|message| syn::Error::new(path.span(), message))?;
+
+    // Joining an absolute path replaces the manifest directory entirely
+    Ok(PathBuf::from(&manifest_dir).join(expanded))
+}
+
+/// Expand `$VAR` and `${VAR}` references using the compiler's environment, and `$$` to `$`.
+fn expand_env(value: &str) -> Result<String, String> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
 
-            fn iter() -> impl ::core::iter::Iterator<Item = &'static str> {
-                #iter_expr
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            expanded.push(c);
+            continue;
+        }
+
+        // `$$` is an escaped, literal `$`
+        if chars.next_if_eq(&'$').is_some() {
+            expanded.push('$');
+            continue;
+        }
+
+        let name = if chars.peek() == Some(&'{') {
+            chars.next();
+
+            let mut name = String::new();
+            loop {
+                match chars.next() {
+                    Some('}') => break,
+                    Some(c) => name.push(c),
+                    None => return Err(format!("Unterminated `${{` in path: {value}")),
+                }
+            }
+            name
+        } else {
+            let mut name = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_ascii_alphanumeric() || c == '_' {
+                    name.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
             }
+            name
+        };
+
+        if name.is_empty() {
+            return Err(format!(
+                "Expected an environment variable name after `$` in path: {value}"
+            ));
+        }
+
+        let var = std::env::var(&name).map_err(|_| {
+            format!("Environment variable `{name}` is not set (used in path: {value})")
+        })?;
+        expanded.push_str(&var);
+    }
+
+    Ok(expanded)
+}
+
+/// Fail compilation if any file or the total embedded size exceeds its configured budget.
+fn check_budgets(ast: &AssetAst, files: &[FileInfo], totals: &Totals) -> Result<(), syn::Error> {
+    let mut errors: Option<syn::Error> = None;
+    let mut push_error = |error: syn::Error| match &mut errors {
+        Some(errors) => errors.combine(error),
+        None => errors = Some(error),
+    };
+
+    if let Some(max_file_size) = &ast.max_file_size {
+        let limit = max_file_size.base10_parse::<u64>()?;
 
-            fn len() -> usize {
-                #len
+        for file in files {
+            let size = file.contents.len() as u64;
+
+            if size > limit {
+                push_error(syn::Error::new(
+                    max_file_size.span(),
+                    format!(
+                        "{} is {size} bytes, exceeding `max_file_size` of {limit} bytes",
+                        file.relative_path
+                    ),
+                ));
             }
         }
-    })
+    }
+
+    if let Some(max_total_size) = &ast.max_total_size {
+        let limit = max_total_size.base10_parse::<u64>()?;
+        let size = totals.embedded_size as u64;
+
+        if size > limit {
+            push_error(syn::Error::new(
+                max_total_size.span(),
+                format!(
+                    "Embedded assets are {size} bytes, exceeding `max_total_size` of {limit} bytes"
+                ),
+            ));
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+/// File contents grouped by content hash, so identical files are embedded once.
+struct Deduplicated {
+    /// One static per unique content, in order of first appearance.
+    statics: Vec<TokenStream>,
+
+    /// Name of the static holding each file's contents, indexed like the input files.
+    contents: Vec<Ident>,
+
+    /// Number of files whose contents were already embedded for another path.
+    duplicate_files: usize,
+
+    /// Bytes not embedded thanks to deduplication.
+    saved_bytes: usize,
 }
 
+fn deduplicate(files: &[FileInfo]) -> Deduplicated {
+    let mut by_hash: HashMap<&str, Ident> = HashMap::new();
+    let mut deduplicated = Deduplicated {
+        statics: Vec::new(),
+        contents: Vec::with_capacity(files.len()),
+        duplicate_files: 0,
+        saved_bytes: 0,
+    };
+
+    for file in files {
+        if let Some(ident) = by_hash.get(file.content_hash.as_str()) {
+            deduplicated.contents.push(ident.clone());
+            deduplicated.duplicate_files += 1;
+            deduplicated.saved_bytes += file.contents.len();
+            continue;
+        }
+
+        let ident = format_ident!("CONTENTS_{}", deduplicated.statics.len());
+        let contents = &file.contents;
+        deduplicated.statics.push(quote! {
+            static #ident: &[u8] = &[#(#contents),*];
+        });
+
+        by_hash.insert(&file.content_hash, ident.clone());
+        deduplicated.contents.push(ident);
+    }
+
+    deduplicated
+}
+
+/// Embed the deduplicated contents and a `FILES` slice holding every file, sorted by path.
+fn files_static(files: &[FileInfo], deduplicated: &Deduplicated) -> TokenStream {
+    let statics = &deduplicated.statics;
+    let file_exprs = files
+        .iter()
+        .zip(&deduplicated.contents)
+        .map(|(file, contents)| file_expr(file, contents));
+
+    quote! {
+        #(#statics)*
+
+        static FILES: &[::axum_asset::EmbeddedFile] = &[#(#file_exprs),*];
+    }
+}
+
+/// Map each path to its index in `FILES`.
 fn get_expr(files: &[FileInfo]) -> TokenStream {
     if files.is_empty() {
         return quote! {
@@ -43,51 +452,102 @@ fn get_expr(files: &[FileInfo]) -> TokenStream {
         };
     }
 
-    let file_exprs: Vec<_> = files.iter().map(get_file_expr).collect();
+    let arms = files.iter().enumerate().map(|(index, file)| {
+        let path = &file.relative_path;
+        quote! { #path => ::core::option::Option::Some(FILES[#index]), }
+    });
 
     quote! {
         match path {
-            #(#file_exprs)*
+            #(#arms)*
             _ => ::core::option::Option::None,
         }
     }
 }
 
-fn iter_expr(files: &[FileInfo]) -> TokenStream {
-    if files.is_empty() {
-        return quote! {
-            ::core::iter::IntoIterator::into_iter([])
-        };
+/// Look up the precomputed children of a directory.
+fn read_dir_expr(files: &[FileInfo]) -> TokenStream {
+    // Directory path (without slashes, `""` for the root) to its children as `(path, is_dir)`, sorted by path
+    let mut directories: BTreeMap<&str, BTreeMap<&str, bool>> = BTreeMap::new();
+    directories.entry("").or_default();
+
+    for file in files {
+        let path = file.relative_path.as_str();
+        let mut child = path;
+        let mut is_dir = false;
+
+        for (index, _) in path.match_indices('/').rev() {
+            directories
+                .entry(&path[..index])
+                .or_default()
+                .insert(child, is_dir);
+            child = &path[..index];
+            is_dir = true;
+        }
+        directories.entry("").or_default().insert(child, is_dir);
     }
 
-    let relative_paths: Vec<_> = files.iter().map(|f| f.relative_path.as_str()).collect();
+    let entries = directories.iter().map(|(dir, children)| {
+        let children = children
+            .iter()
+            .map(|(path, is_dir)| quote! { (#path, #is_dir) });
+
+        quote! { (#dir, &[#(#children),*]) }
+    });
 
     quote! {
-        ::core::iter::IntoIterator::into_iter([
-            #(#relative_paths),*
-        ])
+        const DIRECTORIES: &[(&str, &[(&str, bool)])] = &[#(#entries),*];
+
+        let path = path.trim_matches('/');
+        let children: &[(&str, bool)] =
+            match DIRECTORIES.binary_search_by(|(dir, _)| (*dir).cmp(path)) {
+                ::core::result::Result::Ok(index) => DIRECTORIES[index].1,
+                ::core::result::Result::Err(_) => &[],
+            };
+
+        children.iter().filter_map(|&(path, is_dir)| {
+            if is_dir {
+                ::core::option::Option::Some(::axum_asset::DirEntry::Dir(path))
+            } else {
+                <Self as ::axum_asset::Asset>::get(path).map(::axum_asset::DirEntry::File)
+            }
+        })
     }
 }
 
-fn get_file_expr(file: &FileInfo) -> TokenStream {
+fn file_expr(file: &FileInfo, contents: &Ident) -> TokenStream {
     let content_hash = &file.content_hash;
+    let content_digest = &file.content_digest;
     let last_modified = file.last_modified;
     let mime_type = &file.mime_type;
+    let charset = option_expr(file.charset.as_deref());
+    let integrity = &file.integrity;
+    let etag = &file.etag;
 
     let path = &file.relative_path;
-    let contents = &file.contents;
-    let size = contents.len() as u64;
+    let size = file.contents.len() as u64;
 
     quote! {
-        #path => ::core::option::Option::Some(::axum_asset::EmbeddedFile {
+        ::axum_asset::EmbeddedFile {
             path: #path,
-            contents: &[#(#contents),*],
+            contents: #contents,
             metadata: ::axum_asset::EmbeddedFileMetadata {
                 content_hash: #content_hash,
+                content_digest: [#(#content_digest),*],
                 last_modified: #last_modified,
                 mime_type: #mime_type,
+                charset: #charset,
+                integrity: #integrity,
+                etag: #etag,
                 size: #size,
             },
-        }),
+        }
+    }
+}
+
+fn option_expr(value: Option<&str>) -> TokenStream {
+    match value {
+        Some(value) => quote! { ::core::option::Option::Some(#value) },
+        None => quote! { ::core::option::Option::None },
     }
 }
@@ -1,11 +1,72 @@
 use std::{fs, path::Path, time::UNIX_EPOCH};
 
+use base64::{Engine, prelude::BASE64_STANDARD};
 use proc_macro2::Span;
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha384, Sha512};
 use walkdir::WalkDir;
 
+use crate::ast::{EtagHash, IntegrityAlgorithm, MimeOverride, MimeTarget};
+
+/// Options controlling how files are loaded.
+pub struct LoadOptions<'a> {
+    /// Explicit MIME type overrides.
+    pub mime_overrides: &'a [MimeOverride],
+
+    /// Detect MIME types from file contents when the extension is missing or unknown.
+    pub sniff: bool,
+
+    /// Remove a leading UTF-8 byte order mark from textual files.
+    pub strip_bom: bool,
+
+    /// Convert CRLF line endings to LF in textual files.
+    pub normalize_newlines: bool,
+
+    /// Algorithms used to compute Subresource Integrity metadata.
+    pub integrity: &'a [IntegrityAlgorithm],
+
+    /// Hash algorithm used to compute `ETag` values.
+    pub etag_hash: EtagHash,
+}
+
+/// How paths inside a source directory map to the paths files are served at.
+pub struct PathMapping<'a> {
+    /// Leading directory removed from paths under it, without leading or trailing slashes.
+    pub strip: &'a str,
+
+    /// Prefix prepended to every path, without leading or trailing slashes.
+    pub prefix: &'a str,
+}
+
+impl PathMapping<'_> {
+    /// Map a path relative to the source directory to the served path.
+    pub fn apply(&self, relative_path: String) -> String {
+        // Only strip whole path segments, so `build` does not match `builder/app.js`
+        let relative_path = match relative_path
+            .strip_prefix(self.strip)
+            .and_then(|path| path.strip_prefix('/'))
+        {
+            Some(path) if !self.strip.is_empty() => path.to_string(),
+            _ => relative_path,
+        };
+
+        if self.prefix.is_empty() {
+            relative_path
+        } else {
+            format!("{}/{relative_path}", self.prefix)
+        }
+    }
+}
+
+/// UTF-8 encoded byte order mark.
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
 /// Collect all files from a directory.
-pub fn collect_files(span: Span, dir: &Path) -> Result<Vec<FileInfo>, syn::Error> {
+pub fn collect_files(
+    span: Span,
+    dir: &Path,
+    mapping: &PathMapping<'_>,
+    options: &LoadOptions<'_>,
+) -> Result<Vec<FileInfo>, syn::Error> {
     if !dir.exists() {
         return Err(syn::Error::new(
             span,
@@ -35,7 +96,7 @@ pub fn collect_files(span: Span, dir: &Path) -> Result<Vec<FileInfo>, syn::Error
             continue;
         }
 
-        let file_info = FileInfo::load(span, dir, entry.path())?;
+        let file_info = FileInfo::load(span, dir, entry.path(), mapping, options)?;
         files.push(file_info);
     }
 
@@ -48,7 +109,7 @@ pub fn collect_files(span: Span, dir: &Path) -> Result<Vec<FileInfo>, syn::Error
 /// Information about a single embedded file.
 #[derive(Debug)]
 pub struct FileInfo {
-    /// Path relative to the asset directory (with forward slashes).
+    /// Path relative to the asset directory (with forward slashes), including any prefix.
     pub relative_path: String,
 
     /// Raw file contents.
@@ -57,16 +118,37 @@ pub struct FileInfo {
     /// SHA-256 hash of the contents (hex-encoded).
     pub content_hash: String,
 
+    /// SHA-256 hash of the contents (raw bytes).
+    pub content_digest: [u8; 32],
+
     /// Unix timestamp of last modification.
     pub last_modified: u64,
 
     /// MIME type.
     pub mime_type: String,
+
+    /// Character encoding of textual files.
+    pub charset: Option<String>,
+
+    /// Subresource Integrity metadata (for example `sha384-<base64>`).
+    pub integrity: String,
+
+    /// Hex-encoded hash used as the `ETag` value.
+    pub etag: String,
+
+    /// Warnings to report at compile time.
+    pub warnings: Vec<String>,
 }
 
 impl FileInfo {
     /// Load a file and compute its metadata.
-    fn load(span: Span, base_dir: &Path, file_path: &Path) -> Result<Self, syn::Error> {
+    fn load(
+        span: Span,
+        base_dir: &Path,
+        file_path: &Path,
+        mapping: &PathMapping<'_>,
+        options: &LoadOptions<'_>,
+    ) -> Result<Self, syn::Error> {
         // Read file contents
         let contents = fs::read(file_path).map_err(|e| {
             syn::Error::new(
@@ -75,12 +157,6 @@ impl FileInfo {
             )
         })?;
 
-        // Compute SHA-256 hash
-        let mut hasher = Sha256::new();
-        hasher.update(&contents);
-        let hash_bytes = hasher.finalize();
-        let content_hash = hex::encode(hash_bytes);
-
         // Get last modified time
         let metadata = fs::metadata(file_path).map_err(|e| {
             syn::Error::new(
@@ -106,18 +182,187 @@ impl FileInfo {
             .map_err(|e| syn::Error::new(span, format!("Failed to compute relative path: {}", e)))?
             .to_string_lossy()
             .replace('\\', "/");
+        let relative_path = mapping.apply(relative_path);
 
-        // Guess MIME type from extension
-        let mime_type = mime_guess::from_path(file_path)
-            .first_or_octet_stream()
-            .to_string();
+        Ok(Self::new(relative_path, contents, last_modified, options))
+    }
+
+    /// Compute metadata for file contents served at `relative_path`.
+    ///
+    /// Textual contents are processed (BOM stripping, newline normalization) before hashing.
+    pub fn new(
+        relative_path: String,
+        mut contents: Vec<u8>,
+        last_modified: u64,
+        options: &LoadOptions<'_>,
+    ) -> Self {
+        let mut warnings = Vec::new();
+
+        // Use an explicit override if one matches, otherwise guess MIME type from extension and fall back to the
+        // file contents if enabled
+        let mime_type = match mime_override(&relative_path, options.mime_overrides) {
+            Some(mime_type) => mime_type,
+            None => {
+                let guessed = mime_guess::from_path(&relative_path).first();
+
+                if options.sniff {
+                    sniff_mime_type(&relative_path, guessed, &contents, &mut warnings)
+                } else {
+                    guessed
+                        .unwrap_or(mime_guess::mime::APPLICATION_OCTET_STREAM)
+                        .to_string()
+                }
+            }
+        };
 
-        Ok(FileInfo {
+        // Validate text encoding, optionally strip the UTF-8 byte order mark and normalize line endings
+        let charset = if axum_asset_common::is_text_mime_type(&mime_type) {
+            if options.strip_bom && contents.starts_with(UTF8_BOM) {
+                contents.drain(..UTF8_BOM.len());
+            }
+
+            if options.normalize_newlines {
+                contents = normalize_newlines(contents);
+            }
+
+            if std::str::from_utf8(&contents).is_ok() {
+                Some("utf-8".to_string())
+            } else {
+                warnings.push(format!(
+                    "{relative_path}: `{mime_type}` file is not valid UTF-8, no charset will be sent"
+                ));
+                None
+            }
+        } else {
+            None
+        };
+
+        // Compute SHA-256 hash
+        let mut hasher = Sha256::new();
+        hasher.update(&contents);
+        let content_digest: [u8; 32] = hasher.finalize().into();
+        let content_hash = hex::encode(content_digest);
+
+        let integrity = integrity(&contents, options.integrity);
+
+        let etag = match options.etag_hash {
+            EtagHash::Sha256 => content_hash.clone(),
+            EtagHash::Blake3 => blake3::hash(&contents).to_hex().to_string(),
+            EtagHash::Xxh3 => format!("{:032x}", xxhash_rust::xxh3::xxh3_128(&contents)),
+        };
+
+        FileInfo {
             relative_path,
             contents,
             content_hash,
+            content_digest,
             last_modified,
             mime_type,
+            charset,
+            integrity,
+            etag,
+            warnings,
+        }
+    }
+}
+
+/// Compute Subresource Integrity metadata, with one space-separated entry per algorithm.
+fn integrity(contents: &[u8], algorithms: &[IntegrityAlgorithm]) -> String {
+    algorithms
+        .iter()
+        .map(|algorithm| match algorithm {
+            IntegrityAlgorithm::Sha256 => {
+                format!(
+                    "sha256-{}",
+                    BASE64_STANDARD.encode(Sha256::digest(contents))
+                )
+            }
+            IntegrityAlgorithm::Sha384 => {
+                format!(
+                    "sha384-{}",
+                    BASE64_STANDARD.encode(Sha384::digest(contents))
+                )
+            }
+            IntegrityAlgorithm::Sha512 => {
+                format!(
+                    "sha512-{}",
+                    BASE64_STANDARD.encode(Sha512::digest(contents))
+                )
+            }
         })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Convert CRLF line endings to LF.
+///
+/// Lone CR characters are left untouched.
+fn normalize_newlines(contents: Vec<u8>) -> Vec<u8> {
+    let mut normalized = Vec::with_capacity(contents.len());
+    let mut bytes = contents.iter().peekable();
+
+    while let Some(&byte) = bytes.next() {
+        if byte == b'\r' && bytes.peek() == Some(&&b'\n') {
+            continue;
+        }
+
+        normalized.push(byte);
+    }
+
+    normalized
+}
+
+/// Determine a MIME type from the extension and the file contents.
+///
+/// The contents are only used when the extension is missing or unknown. If the extension maps to a type with a known
+/// signature and the contents carry a different signature, a warning is recorded and the extension still wins.
+fn sniff_mime_type(
+    relative_path: &str,
+    guessed: Option<mime_guess::Mime>,
+    contents: &[u8],
+    warnings: &mut Vec<String>,
+) -> String {
+    let Some(guessed) = guessed else {
+        return crate::sniff::sniff(contents)
+            .unwrap_or("application/octet-stream")
+            .to_string();
+    };
+
+    let guessed = guessed.to_string();
+
+    if crate::sniff::has_signature(&guessed)
+        && let Some(detected) = crate::sniff::signature(contents)
+        && detected != guessed
+    {
+        warnings.push(format!(
+            "{relative_path}: extension suggests `{guessed}` but contents look like `{detected}`"
+        ));
     }
+
+    guessed
+}
+
+/// Find the MIME type override for a file.
+///
+/// Path overrides take precedence over extension overrides. Extensions are compared case-insensitively.
+fn mime_override(relative_path: &str, mime_overrides: &[MimeOverride]) -> Option<String> {
+    let path_override = mime_overrides.iter().find(|mime_override| {
+        matches!(&mime_override.target, MimeTarget::Path(path) if path.value() == relative_path)
+    });
+
+    let extension = Path::new(relative_path)
+        .extension()
+        .map(|ext| ext.to_string_lossy());
+
+    let extension_override = || {
+        let extension = extension.as_deref()?;
+
+        mime_overrides.iter().find(|mime_override| {
+            matches!(&mime_override.target, MimeTarget::Extension(ext) if ext.value().trim_start_matches('.').eq_ignore_ascii_case(extension))
+        })
+    };
+
+    path_override
+        .or_else(extension_override)
+        .map(|mime_override| mime_override.mime_type.value())
 }
@@ -1,6 +1,10 @@
+mod archive;
 mod ast;
 mod codegen;
 mod file;
+mod report;
+mod sniff;
+mod warning;
 
 use proc_macro::TokenStream;
 use syn::{DeriveInput, parse_macro_input};
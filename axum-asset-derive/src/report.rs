@@ -0,0 +1,125 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, Write as _},
+    path::Path,
+};
+
+use flate2::{Compression, write::GzEncoder};
+use serde_json::json;
+
+use crate::file::FileInfo;
+
+/// Totals across all embedded files.
+pub struct Totals {
+    /// Number of embedded paths.
+    pub files: usize,
+
+    /// Sum of all file sizes.
+    pub size: usize,
+
+    /// Bytes actually embedded in the binary after deduplication.
+    pub embedded_size: usize,
+
+    /// Bytes saved by embedding identical contents once.
+    pub saved_bytes: usize,
+}
+
+/// Write an embedding report to `path`.
+///
+/// The format is chosen from the extension: `.md` produces a Markdown table and anything else produces JSON.
+pub fn write_report(path: &Path, files: &[FileInfo], totals: &Totals) -> io::Result<()> {
+    let gzip_sizes = files
+        .iter()
+        .map(|file| gzip_size(&file.contents))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let report = if path.extension().is_some_and(|ext| ext == "md") {
+        markdown_report(files, &gzip_sizes, totals)
+    } else {
+        json_report(files, &gzip_sizes, totals)
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, report)
+}
+
+fn json_report(files: &[FileInfo], gzip_sizes: &[usize], totals: &Totals) -> String {
+    let files: Vec<_> = files
+        .iter()
+        .zip(gzip_sizes)
+        .map(|(file, gzip_size)| {
+            json!({
+                "path": file.relative_path,
+                "size": file.contents.len(),
+                "gzip_size": gzip_size,
+                "mime_type": file.mime_type,
+                "content_hash": file.content_hash,
+            })
+        })
+        .collect();
+
+    let report = json!({
+        "files": files,
+        "totals": {
+            "files": totals.files,
+            "size": totals.size,
+            "gzip_size": gzip_sizes.iter().sum::<usize>(),
+            "embedded_size": totals.embedded_size,
+            "saved_bytes": totals.saved_bytes,
+        },
+    });
+
+    let mut report = serde_json::to_string_pretty(&report).unwrap_or_default();
+    report.push('\n');
+    report
+}
+
+fn markdown_report(files: &[FileInfo], gzip_sizes: &[usize], totals: &Totals) -> String {
+    let mut report = String::new();
+
+    // Writing to a `String` never fails
+    let _ = writeln!(report, "# Embedded assets\n");
+    let _ = writeln!(report, "| Path | Size | Gzip size | MIME type | SHA-256 |");
+    let _ = writeln!(report, "| --- | ---: | ---: | --- | --- |");
+
+    for (file, gzip_size) in files.iter().zip(gzip_sizes) {
+        let _ = writeln!(
+            report,
+            "| `{}` | {} | {} | `{}` | `{}` |",
+            file.relative_path,
+            file.contents.len(),
+            gzip_size,
+            file.mime_type,
+            file.content_hash,
+        );
+    }
+
+    let _ = writeln!(report, "\n## Totals\n");
+    let _ = writeln!(report, "- Files: {}", totals.files);
+    let _ = writeln!(report, "- Size: {} bytes", totals.size);
+    let _ = writeln!(
+        report,
+        "- Gzip size: {} bytes",
+        gzip_sizes.iter().sum::<usize>()
+    );
+    let _ = writeln!(report, "- Embedded size: {} bytes", totals.embedded_size);
+    let _ = writeln!(
+        report,
+        "- Saved by deduplication: {} bytes",
+        totals.saved_bytes
+    );
+
+    report
+}
+
+/// Size of the contents after gzip compression at the default level.
+fn gzip_size(contents: &[u8]) -> io::Result<usize> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(contents)?;
+
+    Ok(encoder.finish()?.len())
+}
@@ -0,0 +1,70 @@
+/// Magic bytes expected at an offset into a file, as `(offset, bytes)`.
+type Magic = (usize, &'static [u8]);
+
+/// File signatures recognised by [`sniff`], as `(magic bytes, MIME type)`.
+///
+/// A signature matches only when all of its magic bytes are found at their offsets.
+const SIGNATURES: &[(&[Magic], &str)] = &[
+    (&[(0, b"\x89PNG\r\n\x1a\n")], "image/png"),
+    (&[(0, b"GIF87a")], "image/gif"),
+    (&[(0, b"GIF89a")], "image/gif"),
+    (&[(0, b"\xff\xd8\xff")], "image/jpeg"),
+    (&[(0, b"RIFF"), (8, b"WEBP")], "image/webp"),
+    (&[(0, b"%PDF-")], "application/pdf"),
+    (&[(0, b"\0asm")], "application/wasm"),
+    (&[(0, b"\x1f\x8b")], "application/gzip"),
+];
+
+/// Detect a MIME type from the contents of a file.
+///
+/// Binary formats are detected from their magic bytes. Text is only classified as HTML or JSON when it looks
+/// unambiguously like one of them.
+pub fn sniff(contents: &[u8]) -> Option<&'static str> {
+    signature(contents).or_else(|| text_heuristic(contents))
+}
+
+/// Check whether a MIME type can be detected from magic bytes.
+///
+/// Only these types are compared against the extension when warning about misnamed files, since heuristics for text
+/// formats are too unreliable to second-guess an extension.
+pub fn has_signature(mime_type: &str) -> bool {
+    SIGNATURES
+        .iter()
+        .any(|(_, signature_mime_type)| *signature_mime_type == mime_type)
+}
+
+/// Detect a binary format from its magic bytes.
+pub fn signature(contents: &[u8]) -> Option<&'static str> {
+    SIGNATURES
+        .iter()
+        .find(|(magics, _)| {
+            magics.iter().all(|(offset, magic)| {
+                contents
+                    .get(*offset..)
+                    .is_some_and(|rest| rest.starts_with(magic))
+            })
+        })
+        .map(|(_, mime_type)| *mime_type)
+}
+
+fn text_heuristic(contents: &[u8]) -> Option<&'static str> {
+    let text = std::str::from_utf8(contents).ok()?;
+    let text = text.trim_start_matches('\u{feff}').trim();
+
+    let lowercase_prefix = text
+        .chars()
+        .take(14)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if lowercase_prefix.starts_with("<!doctype html") || lowercase_prefix.starts_with("<html") {
+        return Some("text/html");
+    }
+
+    if (text.starts_with('{') && text.ends_with('}'))
+        || (text.starts_with('[') && text.ends_with(']'))
+    {
+        return Some("application/json");
+    }
+
+    None
+}
@@ -0,0 +1,16 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+
+/// Generate tokens that produce a compile-time warning with the given message.
+///
+/// Proc macros can't emit warnings on stable Rust, so this expands to the use of a deprecated constant whose
+/// deprecation note carries the message.
+pub fn warning(span: Span, message: &str) -> TokenStream {
+    quote_spanned! {span=>
+        const _: () = {
+            #[deprecated(note = #message)]
+            const ASSET_WARNING: () = ();
+            ASSET_WARNING
+        };
+    }
+}
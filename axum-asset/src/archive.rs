@@ -0,0 +1,100 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt,
+    io::{self, Read, Seek},
+    path::Path,
+    sync::Arc,
+};
+
+use axum_asset_common::archive::{self, ArchiveEntry};
+
+use crate::{AssetRef, AssetSource, source::OwnedFile};
+
+/// An [`AssetSource`] that serves the files of a zip or tar archive.
+///
+/// All entries are read into memory and indexed when the archive is opened, so requests never touch the archive again.
+/// Files are served with the same caching headers as embedded files; `Last-Modified` comes from each entry's
+/// timestamp. Directory entries and entries whose paths are absolute or contain `..` are skipped.
+///
+/// Requires the `archive` feature. To embed the files of an archive at compile time instead, use
+/// `#[asset(archive = "dist.zip")]` with the derive macro.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+///
+/// use axum::Router;
+/// use axum_asset::{ArchiveSource, AssetService};
+///
+/// let source = ArchiveSource::open("dist.zip").expect("failed to open dist.zip");
+/// let app: Router = Router::new().nest_service("/static", AssetService::new(Arc::new(source)));
+/// ```
+pub struct ArchiveSource {
+    files: BTreeMap<String, Arc<OwnedFile>>,
+}
+
+impl ArchiveSource {
+    /// Open an archive on disk.
+    ///
+    /// The format is chosen from the extension: `.zip`, `.tar`, or `.tar.gz`/`.tgz`.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        archive::read_path(path.as_ref()).map(Self::from_entries)
+    }
+
+    /// Read a zip archive, for example `Cursor::new(include_bytes!("dist.zip"))`.
+    pub fn from_zip(reader: impl Read + Seek) -> io::Result<Self> {
+        archive::read_zip(reader).map(Self::from_entries)
+    }
+
+    /// Read an uncompressed tar archive.
+    pub fn from_tar(reader: impl Read) -> io::Result<Self> {
+        archive::read_tar(reader).map(Self::from_entries)
+    }
+
+    /// Read a gzip-compressed tar archive.
+    pub fn from_tar_gz(reader: impl Read) -> io::Result<Self> {
+        archive::read_tar_gz(reader).map(Self::from_entries)
+    }
+
+    fn from_entries(entries: Vec<ArchiveEntry>) -> Self {
+        let files = entries
+            .into_iter()
+            .map(|entry| {
+                let file = OwnedFile::new(entry.path.clone(), entry.contents, entry.last_modified);
+                (entry.path, Arc::new(file))
+            })
+            .collect();
+
+        Self { files }
+    }
+
+    /// Return the number of files in the archive.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Check if the archive contains no files.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+impl AssetSource for ArchiveSource {
+    fn get(&self, path: &str) -> Option<AssetRef> {
+        self.files.get(path).cloned().map(AssetRef::from_owned)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'static, str>> + '_> {
+        Box::new(self.files.keys().map(|path| Cow::Owned(path.clone())))
+    }
+}
+
+impl fmt::Debug for ArchiveSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchiveSource")
+            .field("files", &self.files.len())
+            .finish()
+    }
+}
@@ -1,14 +1,16 @@
+use std::{collections::HashMap, io, path::Path as FsPath, sync::Arc};
+
 use axum::{
     Router,
-    http::{HeaderMap, header::IF_NONE_MATCH},
+    extract::{OriginalUri, Path},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
     routing::get,
 };
-use axum_extra::{
-    TypedHeader,
-    headers::{IfModifiedSince, IfNoneMatch},
-};
 
-use crate::EmbeddedFile;
+use crate::{
+    AssetRef, Conditional, DirEntry, EmbeddedFile, ExtractOptions, GlobError, ServeOptions,
+};
 
 /// Trait for types that provide access to embedded static assets.
 pub trait Asset {
@@ -20,22 +22,145 @@ pub trait Asset {
     /// Iterate over all embedded files.
     fn iter() -> impl Iterator<Item = &'static str>;
 
+    /// Iterate over all embedded files, in the order of [`Asset::iter`].
+    ///
+    /// Prefer this over calling [`Asset::get`] for every path: the derive macro yields the files straight from a
+    /// static slice.
+    fn files() -> impl Iterator<Item = EmbeddedFile> {
+        Self::iter().filter_map(Self::get)
+    }
+
     /// Return the number of embedded files.
     fn len() -> usize;
 
+    /// Return the combined size of all embedded files in bytes.
+    ///
+    /// Files with identical contents are counted once per path, even if their contents are embedded once.
+    fn total_size() -> u64 {
+        Self::files().map(|file| file.metadata.size).sum()
+    }
+
     /// Check if the asset collection is empty.
     fn is_empty() -> bool {
         Self::len() == 0
     }
 
+    /// Iterate over the immediate children of a directory, sorted by path.
+    ///
+    /// The directory path is relative to the embedded directory; leading and trailing slashes are ignored, so
+    /// `"nested/"`, `"/nested"` and `"nested"` are equivalent and `""` is the root. Unknown directories yield no
+    /// entries.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use axum_asset::{Asset, DirEntry};
+    ///
+    /// #[derive(Asset)]
+    /// #[asset(dir = "tests/static")]
+    /// struct StaticAssets;
+    ///
+    /// for entry in StaticAssets::read_dir("nested/") {
+    ///     match entry {
+    ///         DirEntry::Dir(path) => println!("{path}/"),
+    ///         DirEntry::File(file) => println!("{} ({} bytes)", file.path, file.metadata.size),
+    ///     }
+    /// }
+    /// ```
+    fn read_dir(path: &str) -> impl Iterator<Item = DirEntry> {
+        crate::dir::children(Self::iter(), crate::dir::normalize(path))
+            .into_iter()
+            .filter_map(|(path, is_dir)| match is_dir {
+                true => Some(DirEntry::Dir(path)),
+                false => Self::get(path).map(DirEntry::File),
+            })
+    }
+
+    /// Iterate over all files inside a directory at any depth, sorted by path.
+    ///
+    /// The directory path is normalized like in [`Asset::read_dir`]; `""` yields every file.
+    fn walk(prefix: &str) -> impl Iterator<Item = EmbeddedFile> {
+        let prefix = crate::dir::normalize(prefix);
+
+        // Combinators may yield files out of order, so sort like `read_dir` does
+        let mut files: Vec<_> = Self::files()
+            .filter(|file| crate::dir::is_under(prefix, file.path))
+            .collect();
+        files.sort_unstable_by_key(|file| file.path);
+
+        files.into_iter()
+    }
+
+    /// Iterate over the files whose paths match a glob pattern, in the order of [`Asset::iter`].
+    ///
+    /// The pattern is compiled once per call and matched against paths relative to the embedded directory. `*` and
+    /// `?` do not match `/`, so `theme/*.css` only matches stylesheets directly inside `theme` while `**/*.css` matches
+    /// them at any depth. Character classes (`[a-z]`) and alternatives (`{css,scss}`) are supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GlobError`] if the pattern is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use axum_asset::Asset;
+    ///
+    /// #[derive(Asset)]
+    /// #[asset(dir = "tests/static")]
+    /// struct StaticAssets;
+    ///
+    /// for stylesheet in StaticAssets::glob("**/*.css").unwrap() {
+    ///     println!("<link rel=\"stylesheet\" href=\"/static/{}\">", stylesheet.path);
+    /// }
+    /// ```
+    fn glob(pattern: &str) -> Result<impl Iterator<Item = EmbeddedFile>, GlobError> {
+        let matcher = crate::glob::matcher(pattern)?;
+
+        Ok(Self::files().filter(move |file| matcher.is_match(file.path)))
+    }
+
+    /// Check if a directory exists, meaning at least one file is embedded inside it. The root always exists.
+    fn exists_dir(path: &str) -> bool {
+        crate::dir::normalize(path).is_empty() || Self::read_dir(path).next().is_some()
+    }
+
+    /// Write every embedded file into `dir`, creating it if needed.
+    ///
+    /// Files keep their relative paths and their modification times are set to
+    /// [`last_modified`](crate::EmbeddedFileMetadata::last_modified), so the directory contains exactly the bytes that
+    /// are served, for example to upload them to a CDN origin.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use axum_asset::Asset;
+    ///
+    /// #[derive(Asset)]
+    /// #[asset(dir = "tests/static")]
+    /// struct StaticAssets;
+    ///
+    /// StaticAssets::extract_to("dist").expect("failed to extract assets");
+    /// ```
+    fn extract_to(dir: impl AsRef<FsPath>) -> io::Result<()> {
+        Self::extract_with(dir, &ExtractOptions::default())
+    }
+
+    /// Write every embedded file into `dir` using the given [`ExtractOptions`].
+    fn extract_with(dir: impl AsRef<FsPath>, options: &ExtractOptions) -> io::Result<()> {
+        crate::extract::extract::<Self>(dir.as_ref(), options)
+    }
+
     /// Creates an Axum [`Router`] that serves all embedded files.
     ///
-    /// Each embedded file is mounted at its relative path (prefixed with `/`). The router automatically handles HTTP
-    /// caching:
+    /// Each embedded file is mounted at its relative path (prefixed with `/`), percent-encoded where the path contains
+    /// characters that cannot appear in a URL, so `my file.txt` is served at `/my%20file.txt`. The router automatically
+    /// handles HTTP caching:
     ///
     /// - Sets `ETag`, `Last-Modified`, and `Cache-Control` response headers
     /// - Handles `If-None-Match` and `If-Modified-Since` conditional requests
     /// - Returns `304 Not Modified` when the client's cached version is still valid
+    /// - Serves single byte ranges requested with `Range` as `206 Partial Content`
     ///
     /// # Example
     ///
@@ -48,7 +173,7 @@ pub trait Asset {
     /// struct StaticAssets;
     ///
     /// // Mount assets at /static
-    /// let app = Router::new().nest("/static", StaticAssets::router());
+    /// let app: Router = Router::new().nest("/static", StaticAssets::router());
     ///
     /// // Files are now accessible at /static/index.html, /static/css/style.css, etc.
     /// ```
@@ -56,24 +181,97 @@ pub trait Asset {
     where
         T: Clone + Send + Sync + 'static,
     {
+        Self::router_with(ServeOptions::default())
+    }
+
+    /// Creates an Axum [`Router`] that serves all embedded files using the given [`ServeOptions`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use axum::Router;
+    /// use axum_asset::{Asset, ServeOptions};
+    ///
+    /// #[derive(Asset)]
+    /// #[asset(dir = "tests/static")]
+    /// struct StaticAssets;
+    ///
+    /// // Send a `Repr-Digest` header with every response
+    /// let options = ServeOptions::new().with_repr_digest();
+    /// let app: Router = Router::new().nest("/static", StaticAssets::router_with(options));
+    /// ```
+    fn router_with<T>(options: ServeOptions) -> Router<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let options = Arc::new(options);
         let mut router = Router::<T>::new();
 
-        for file_name in Self::iter() {
-            let file = Self::get(file_name).unwrap();
-            let route = format!("/{}", file.path);
+        for file in Self::files() {
+            let file = AssetRef::from(file);
+            // Requests arrive percent-encoded, so route files by their encoded path
+            let route = format!("/{}", crate::util::encode_path(file.path()));
 
             router = router.route(
                 &route,
                 get({
-                    move |headers: HeaderMap, if_none_match: Option<TypedHeader<IfNoneMatch>>, if_modified_since: Option<TypedHeader<IfModifiedSince>>| async move {
-                        // Workaround for https://github.com/hyperium/headers/issues/204
-                        // IfNoneMatch::decode returns Some even when header is absent
-                        let if_none_match = if headers.contains_key(IF_NONE_MATCH) {
-                            if_none_match
-                        } else {
-                            None
-                        };
-                        crate::util::respond(if_none_match, if_modified_since, file)
+                    let options = options.clone();
+                    move |conditional: Conditional| async move {
+                        crate::util::respond(
+                            &conditional,
+                            &file,
+                            &options,
+                            crate::util::cache_control(),
+                        )
+                    }
+                }),
+            );
+        }
+
+        if options.directory_listing() {
+            for (dir, entries) in crate::listing::directories::<Self>() {
+                let entries: Arc<[DirEntry]> = entries.into();
+                let handler = get(
+                    move |OriginalUri(uri): OriginalUri, headers: HeaderMap| async move {
+                        crate::listing::respond(&headers, uri.path(), dir, &entries)
+                    },
+                );
+
+                router = if dir.is_empty() {
+                    router.route("/", handler)
+                } else {
+                    let dir = crate::util::encode_path(dir);
+                    router
+                        .route(&format!("/{dir}"), handler.clone())
+                        .route(&format!("/{dir}/"), handler)
+                };
+            }
+        }
+
+        if let Some(content_route) = options.content_route() {
+            // Files with identical contents share a hash; the first one wins
+            let mut files_by_hash = HashMap::new();
+            for file in Self::files() {
+                files_by_hash
+                    .entry(file.metadata.content_hash)
+                    .or_insert_with(|| AssetRef::from(file));
+            }
+            let files_by_hash = Arc::new(files_by_hash);
+
+            router = router.route(
+                &format!("{content_route}/{{hash}}"),
+                get({
+                    let options = options.clone();
+                    move |Path(hash): Path<String>, conditional: Conditional| async move {
+                        match files_by_hash.get(hash.as_str()) {
+                            Some(file) => crate::util::respond(
+                                &conditional,
+                                file,
+                                &options,
+                                crate::util::immutable_cache_control(),
+                            ),
+                            None => StatusCode::NOT_FOUND.into_response(),
+                        }
                     }
                 }),
             );
@@ -0,0 +1,227 @@
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{OnceLock, PoisonError, RwLock},
+};
+
+use crate::{Asset, EmbeddedFile};
+
+/// Combines two asset types, with files from `A` shadowing files with the same path in `B`.
+///
+/// Useful for theming: `B` provides the base set and `A` overrides a few files. Like the other combinators, a value
+/// can be created with [`Default`] to use the composition as an [`AssetSource`](crate::AssetSource).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::Router;
+/// use axum_asset::{Asset, Overlay};
+///
+/// #[derive(Asset)]
+/// #[asset(dir = "tests/static")]
+/// struct BaseAssets;
+///
+/// #[derive(Asset)]
+/// #[asset(dir = "tests/theme")]
+/// struct ThemeAssets;
+///
+/// let app: Router = Router::new().nest("/static", Overlay::<ThemeAssets, BaseAssets>::router());
+/// ```
+pub struct Overlay<A, B>(PhantomData<fn() -> (A, B)>);
+
+impl<A, B> Default for Overlay<A, B> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Asset, B: Asset> Asset for Overlay<A, B> {
+    fn get(path: &str) -> Option<EmbeddedFile> {
+        A::get(path).or_else(|| B::get(path))
+    }
+
+    fn iter() -> impl Iterator<Item = &'static str> {
+        A::iter().chain(B::iter().filter(|path| A::get(path).is_none()))
+    }
+
+    fn files() -> impl Iterator<Item = EmbeddedFile> {
+        A::files().chain(B::files().filter(|file| A::get(file.path).is_none()))
+    }
+
+    fn len() -> usize {
+        Self::iter().count()
+    }
+}
+
+/// A path prefix used by [`Prefixed`].
+pub trait Prefix {
+    /// Prefix prepended to every path. Leading and trailing slashes are ignored.
+    const PREFIX: &'static str;
+}
+
+/// Serves the files of `A` under the path prefix given by `P`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum_asset::{Asset, Prefix, Prefixed};
+///
+/// #[derive(Asset)]
+/// #[asset(dir = "tests/static")]
+/// struct StaticAssets;
+///
+/// struct V2;
+///
+/// impl Prefix for V2 {
+///     const PREFIX: &'static str = "v2";
+/// }
+///
+/// assert!(Prefixed::<StaticAssets, V2>::get("v2/index.html").is_some());
+/// ```
+pub struct Prefixed<A, P>(PhantomData<fn() -> (A, P)>);
+
+impl<A, P> Default for Prefixed<A, P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Unprefixed path of each file to its prefixed path.
+type PathTable = HashMap<&'static str, &'static str>;
+
+impl<A: Asset + 'static, P: Prefix + 'static> Prefixed<A, P> {
+    fn prefix() -> &'static str {
+        P::PREFIX.trim_matches('/')
+    }
+
+    /// Map a path of `A` to its prefixed path, or `None` if `A` has no such file.
+    fn prefixed(path: &'static str) -> Option<&'static str> {
+        match Self::prefix() {
+            "" => Some(path),
+            _ => Self::paths().get(path).copied(),
+        }
+    }
+
+    /// Table from each path of `A` to its prefixed path, built on first use.
+    ///
+    /// Paths of embedded files are `&'static str`, so prefixed paths are leaked, once per file of each `Prefixed` type.
+    fn paths() -> &'static PathTable {
+        // Statics in generic functions are shared by every instantiation, so key the tables by type
+        static TABLES: OnceLock<RwLock<HashMap<TypeId, &'static PathTable>>> = OnceLock::new();
+
+        let tables = TABLES.get_or_init(Default::default);
+        let key = TypeId::of::<Self>();
+
+        if let Some(table) = tables
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+        {
+            return table;
+        }
+
+        let mut tables = tables.write().unwrap_or_else(PoisonError::into_inner);
+        tables.entry(key).or_insert_with(|| {
+            let prefix = Self::prefix();
+            let table = A::iter()
+                .map(|path| {
+                    let prefixed: &'static str = Box::leak(format!("{prefix}/{path}").into());
+                    (path, prefixed)
+                })
+                .collect();
+
+            Box::leak(Box::new(table))
+        })
+    }
+}
+
+impl<A: Asset + 'static, P: Prefix + 'static> Asset for Prefixed<A, P> {
+    fn get(path: &str) -> Option<EmbeddedFile> {
+        let inner_path = match Self::prefix() {
+            "" => path,
+            prefix => path.strip_prefix(prefix)?.strip_prefix('/')?,
+        };
+
+        let file = A::get(inner_path)?;
+
+        Some(EmbeddedFile {
+            path: Self::prefixed(file.path)?,
+            ..file
+        })
+    }
+
+    fn iter() -> impl Iterator<Item = &'static str> {
+        A::iter().filter_map(Self::prefixed)
+    }
+
+    fn files() -> impl Iterator<Item = EmbeddedFile> {
+        A::files().filter_map(|file| {
+            Some(EmbeddedFile {
+                path: Self::prefixed(file.path)?,
+                ..file
+            })
+        })
+    }
+
+    fn len() -> usize {
+        A::len()
+    }
+
+    fn total_size() -> u64 {
+        A::total_size()
+    }
+}
+
+/// A path predicate used by [`Filtered`].
+pub trait Filter {
+    /// Returns `true` if the file at `path` should be included.
+    fn matches(path: &str) -> bool;
+}
+
+/// Serves only the files of `A` whose paths match the filter `F`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum_asset::{Asset, Filter, Filtered};
+///
+/// #[derive(Asset)]
+/// #[asset(dir = "tests/static")]
+/// struct StaticAssets;
+///
+/// struct Stylesheets;
+///
+/// impl Filter for Stylesheets {
+///     fn matches(path: &str) -> bool {
+///         path.ends_with(".css")
+///     }
+/// }
+///
+/// assert!(Filtered::<StaticAssets, Stylesheets>::get("index.html").is_none());
+/// ```
+pub struct Filtered<A, F>(PhantomData<fn() -> (A, F)>);
+
+impl<A, F> Default for Filtered<A, F> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Asset, F: Filter> Asset for Filtered<A, F> {
+    fn get(path: &str) -> Option<EmbeddedFile> {
+        A::get(path).filter(|_| F::matches(path))
+    }
+
+    fn iter() -> impl Iterator<Item = &'static str> {
+        A::iter().filter(|path| F::matches(path))
+    }
+
+    fn files() -> impl Iterator<Item = EmbeddedFile> {
+        A::files().filter(|file| F::matches(file.path))
+    }
+
+    fn len() -> usize {
+        Self::iter().count()
+    }
+}
@@ -0,0 +1,96 @@
+use std::convert::Infallible;
+
+use axum::{
+    extract::FromRequestParts,
+    http::{HeaderMap, header::IF_NONE_MATCH, request::Parts},
+};
+use axum_extra::headers::{HeaderMapExt, IfModifiedSince, IfNoneMatch, IfRange, Range};
+
+/// Conditional and range request headers, extracted from a request.
+///
+/// Pass it to [`EmbeddedFile::respond_to`](crate::EmbeddedFile::respond_to) to answer a request from your own handler
+/// exactly like [`Asset::router`](crate::Asset::router) does:
+///
+/// - `If-None-Match` and `If-Modified-Since` produce `304 Not Modified` when the client's copy is still valid
+/// - A single `Range` is served as `206 Partial Content`, unless `If-Range` no longer matches the file
+/// - An unsatisfiable `Range` produces `416 Range Not Satisfiable`
+///
+/// Extracting it never fails; missing or malformed headers are treated as absent.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{Router, response::Response, routing::get};
+/// use axum_asset::{Asset, Conditional};
+///
+/// #[derive(Asset)]
+/// #[asset(dir = "tests/static")]
+/// struct StaticAssets;
+///
+/// async fn index(conditional: Conditional) -> Response {
+///     StaticAssets::get("index.html").unwrap().respond_to(&conditional)
+/// }
+///
+/// let app: Router = Router::new().route("/", get(index));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Conditional {
+    if_none_match: Option<IfNoneMatch>,
+    if_modified_since: Option<IfModifiedSince>,
+    range: Option<Range>,
+    if_range: Option<IfRange>,
+    wants_repr_digest: bool,
+}
+
+impl Conditional {
+    /// Read the conditional and range headers from a request's headers.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        // Workaround for https://github.com/hyperium/headers/issues/204
+        // IfNoneMatch::decode returns Some even when header is absent
+        let if_none_match = match headers.contains_key(IF_NONE_MATCH) {
+            true => headers.typed_get(),
+            false => None,
+        };
+
+        Self {
+            if_none_match,
+            if_modified_since: headers.typed_get(),
+            range: headers.typed_get(),
+            if_range: headers.typed_get(),
+            wants_repr_digest: crate::util::wants_repr_digest(headers),
+        }
+    }
+
+    /// The `If-None-Match` header, if present.
+    pub fn if_none_match(&self) -> Option<&IfNoneMatch> {
+        self.if_none_match.as_ref()
+    }
+
+    /// The `If-Modified-Since` header, if present.
+    pub fn if_modified_since(&self) -> Option<&IfModifiedSince> {
+        self.if_modified_since.as_ref()
+    }
+
+    /// The `Range` header, if present.
+    pub fn range(&self) -> Option<&Range> {
+        self.range.as_ref()
+    }
+
+    /// The `If-Range` header, if present.
+    pub fn if_range(&self) -> Option<&IfRange> {
+        self.if_range.as_ref()
+    }
+
+    /// Check if the request asked for a SHA-256 `Repr-Digest` with `Want-Repr-Digest`.
+    pub(crate) fn wants_repr_digest(&self) -> bool {
+        self.wants_repr_digest
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Conditional {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+
+use crate::EmbeddedFile;
+
+/// An entry returned by [`Asset::read_dir`](crate::Asset::read_dir).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirEntry {
+    /// A file directly inside the directory.
+    File(EmbeddedFile),
+
+    /// A subdirectory, identified by its path relative to the embedded directory (without a trailing slash).
+    Dir(&'static str),
+}
+
+impl DirEntry {
+    /// Path of the entry relative to the embedded directory.
+    pub fn path(&self) -> &'static str {
+        match self {
+            Self::File(file) => file.path,
+            Self::Dir(path) => path,
+        }
+    }
+
+    /// Name of the entry within its directory (the last path segment).
+    pub fn name(&self) -> &'static str {
+        let path = self.path();
+        path.rsplit('/').next().unwrap_or(path)
+    }
+
+    /// Check if the entry is a directory.
+    pub fn is_dir(&self) -> bool {
+        matches!(self, Self::Dir(_))
+    }
+
+    /// Check if the entry is a file.
+    pub fn is_file(&self) -> bool {
+        matches!(self, Self::File(_))
+    }
+
+    /// The file, if the entry is a file.
+    pub fn file(&self) -> Option<EmbeddedFile> {
+        match self {
+            Self::File(file) => Some(*file),
+            Self::Dir(_) => None,
+        }
+    }
+}
+
+/// Normalize a directory path by removing leading and trailing slashes.
+pub(crate) fn normalize(dir: &str) -> &str {
+    dir.trim_matches('/')
+}
+
+/// Check whether `path` is inside the normalized directory `dir`, at any depth.
+pub(crate) fn is_under(dir: &str, path: &str) -> bool {
+    dir.is_empty()
+        || path
+            .strip_prefix(dir)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Find the immediate children of the normalized directory `dir` among `paths`.
+///
+/// Returns `(path, is_dir)` pairs sorted by path. Directory paths are slices of the file paths, so they stay
+/// `'static`.
+pub(crate) fn children(
+    paths: impl Iterator<Item = &'static str>,
+    dir: &str,
+) -> Vec<(&'static str, bool)> {
+    let offset = if dir.is_empty() { 0 } else { dir.len() + 1 };
+    let mut children = BTreeMap::new();
+
+    for path in paths.filter(|path| is_under(dir, path)) {
+        match path[offset..].find('/') {
+            Some(end) => children.insert(&path[..offset + end], true),
+            None => children.insert(path, false),
+        };
+    }
+
+    children.into_iter().collect()
+}
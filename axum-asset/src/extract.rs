@@ -0,0 +1,175 @@
+use std::{
+    fmt::Write as _,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use crate::{Asset, EmbeddedFile};
+
+/// Options controlling how embedded files are written to disk.
+///
+/// Pass these to [`Asset::extract_with`](crate::Asset::extract_with). The defaults match
+/// [`Asset::extract_to`](crate::Asset::extract_to).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum_asset::{Asset, ExtractOptions};
+///
+/// #[derive(Asset)]
+/// #[asset(dir = "tests/static")]
+/// struct StaticAssets;
+///
+/// let options = ExtractOptions::new().with_manifest("../asset-manifest.json");
+/// StaticAssets::extract_with("dist", &options).expect("failed to extract assets");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ExtractOptions {
+    manifest: Option<PathBuf>,
+}
+
+impl ExtractOptions {
+    /// Create options with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also write a JSON manifest listing every file with its size, MIME type, hashes and modification time.
+    ///
+    /// Relative paths are resolved against the extraction directory.
+    pub fn with_manifest(mut self, path: impl Into<PathBuf>) -> Self {
+        self.manifest = Some(path.into());
+        self
+    }
+
+    /// Path of the manifest, if enabled.
+    pub fn manifest(&self) -> Option<&Path> {
+        self.manifest.as_deref()
+    }
+}
+
+/// Write every file of `A` into `dir`, preserving `last_modified` as the file modification time.
+pub(crate) fn extract<A: Asset + ?Sized>(dir: &Path, options: &ExtractOptions) -> io::Result<()> {
+    let files: Vec<EmbeddedFile> = A::files().collect();
+
+    for file in &files {
+        let target = dir.join(file.path);
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, file.contents)?;
+
+        File::options()
+            .write(true)
+            .open(&target)?
+            .set_modified(UNIX_EPOCH + Duration::from_secs(file.metadata.last_modified))?;
+    }
+
+    if let Some(manifest) = options.manifest() {
+        let target = dir.join(manifest);
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, manifest_json(&files))?;
+    }
+
+    Ok(())
+}
+
+/// Extract the files of `A` if the process was started with `--dump-assets <dir>`.
+///
+/// Returns `Ok(true)` if the files were extracted, so `main` can exit instead of starting the server. A manifest is
+/// written as well when `--dump-manifest <path>` is given. Both flags also accept the `--flag=value` form.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum_asset::Asset;
+///
+/// #[derive(Asset)]
+/// #[asset(dir = "tests/static")]
+/// struct StaticAssets;
+///
+/// fn main() -> std::io::Result<()> {
+///     if axum_asset::dump_assets_from_args::<StaticAssets>()? {
+///         return Ok(());
+///     }
+///
+///     // Start the server...
+///     Ok(())
+/// }
+/// ```
+pub fn dump_assets_from_args<A: Asset>() -> io::Result<bool> {
+    dump_assets_from::<A>(std::env::args().skip(1))
+}
+
+/// Same as [`dump_assets_from_args`], but reads the flags from `args` instead of the process arguments.
+pub fn dump_assets_from<A: Asset>(args: impl IntoIterator<Item = String>) -> io::Result<bool> {
+    let mut dir = None;
+    let mut options = ExtractOptions::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+
+        if flag != "--dump-assets" && flag != "--dump-manifest" {
+            continue;
+        }
+
+        let value = value.or_else(|| args.next()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("`{flag}` requires a path"),
+            )
+        })?;
+
+        if flag == "--dump-assets" {
+            dir = Some(PathBuf::from(value));
+        } else {
+            options = options.with_manifest(value);
+        }
+    }
+
+    match dir {
+        Some(dir) => extract::<A>(&dir, &options).map(|()| true),
+        None => Ok(false),
+    }
+}
+
+/// Render the manifest as pretty-printed JSON.
+fn manifest_json(files: &[EmbeddedFile]) -> String {
+    let mut json = String::from("{\n  \"files\": [");
+
+    for (index, file) in files.iter().enumerate() {
+        let metadata = file.metadata;
+        let separator = if index == 0 { "" } else { "," };
+
+        // Writing to a `String` never fails
+        let _ = write!(
+            json,
+            "{separator}\n    {{\n      \"path\": {},\n      \"size\": {},\n      \"mime_type\": {},\n      \
+             \"content_hash\": {},\n      \"etag\": {},\n      \"integrity\": {},\n      \"last_modified\": {}\n    }}",
+            crate::util::json_string(file.path),
+            metadata.size,
+            crate::util::json_string(metadata.mime_type),
+            crate::util::json_string(metadata.content_hash),
+            crate::util::json_string(metadata.etag),
+            crate::util::json_string(metadata.integrity),
+            metadata.last_modified,
+        );
+    }
+
+    if !files.is_empty() {
+        json.push_str("\n  ");
+    }
+    json.push_str("]\n}\n");
+
+    json
+}
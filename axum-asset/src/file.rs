@@ -1,19 +1,63 @@
+use axum::response::{IntoResponse, Response};
+use base64::{
+    Engine,
+    prelude::{BASE64_STANDARD, BASE64_URL_SAFE_NO_PAD},
+};
+
+use crate::{AssetRef, Conditional, ServeOptions};
+
 /// Metadata about an embedded file.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct EmbeddedFileMetadata {
-    /// SHA-256 hash of the file contents, used for ETag.
+    /// SHA-256 hash of the file contents (hex-encoded).
     pub content_hash: &'static str,
 
+    /// SHA-256 hash of the file contents (raw bytes).
+    pub content_digest: [u8; 32],
+
+    /// Hash of the file contents used for the `ETag` header (hex-encoded).
+    ///
+    /// This is the same as `content_hash` unless a different algorithm is selected with
+    /// `#[asset(etag_hash = "...")]`.
+    pub etag: &'static str,
+
     /// Unix timestamp of last modification.
     pub last_modified: u64,
 
-    /// MIME type derived from file extension.
+    /// MIME type derived from file extension, file contents (with `sniff = true`) or a `mime(...)` override.
     pub mime_type: &'static str,
 
+    /// Character encoding of textual files, appended to the `Content-Type` header.
+    ///
+    /// This is `Some("utf-8")` for textual MIME types whose contents are valid UTF-8 and `None` otherwise.
+    pub charset: Option<&'static str>,
+
+    /// Subresource Integrity metadata, such as `sha384-<base64>`.
+    ///
+    /// Contains one space-separated entry per algorithm configured with `#[asset(integrity = "...")]` and can be used
+    /// directly as the value of an HTML `integrity` attribute.
+    pub integrity: &'static str,
+
     /// Size of the file in bytes.
     pub size: u64,
 }
 
+impl EmbeddedFileMetadata {
+    /// SHA-256 hash of the file contents, encoded as standard base64 with padding.
+    ///
+    /// This is the encoding used by `Repr-Digest` headers and Subresource Integrity metadata.
+    pub fn content_digest_base64(&self) -> String {
+        BASE64_STANDARD.encode(self.content_digest)
+    }
+
+    /// SHA-256 hash of the file contents, encoded as URL-safe base64 without padding.
+    ///
+    /// Useful for cache keys and content-addressed URLs.
+    pub fn content_digest_base64url(&self) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(self.content_digest)
+    }
+}
+
 /// A file embedded at compile time.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct EmbeddedFile {
@@ -26,3 +70,33 @@ pub struct EmbeddedFile {
     /// File metadata.
     pub metadata: EmbeddedFileMetadata,
 }
+
+impl EmbeddedFile {
+    /// Build a response for this file, honoring the request's conditional and range headers.
+    ///
+    /// Uses the same headers as [`Asset::router`](crate::Asset::router), including `304 Not Modified` and
+    /// `206 Partial Content` handling, so the file can be returned from your own handlers. See [`Conditional`] for an
+    /// example.
+    pub fn respond_to(&self, conditional: &Conditional) -> Response {
+        self.respond_with(conditional, &ServeOptions::default())
+    }
+
+    /// Same as [`EmbeddedFile::respond_to`], but using the given [`ServeOptions`], for example to send the same
+    /// `ETag`s as a router created with [`Asset::router_with`](crate::Asset::router_with).
+    pub fn respond_with(&self, conditional: &Conditional, options: &ServeOptions) -> Response {
+        crate::util::respond(
+            conditional,
+            &AssetRef::from(*self),
+            options,
+            crate::util::cache_control(),
+        )
+    }
+}
+
+/// Responds with `200 OK` and the file's caching headers, ignoring conditional requests. Use
+/// [`EmbeddedFile::respond_to`] to answer them with `304 Not Modified`.
+impl IntoResponse for EmbeddedFile {
+    fn into_response(self) -> Response {
+        self.respond_to(&Conditional::default())
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashMap},
+    fmt, fs,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use walkdir::WalkDir;
+
+use crate::{AssetRef, AssetSource, source::OwnedFile};
+
+/// Maximum number of files kept in the [`FsOverride`] cache.
+const CACHE_CAPACITY: usize = 64;
+
+/// An [`AssetSource`] that serves files from a directory on disk, falling back to another source.
+///
+/// Each request checks the override directory first, so a single file can be patched in production without rebuilding
+/// the binary. `ETag`s and `Last-Modified` headers are computed from the file on disk. Loaded files are cached and
+/// reloaded when their size or modification time changes.
+///
+/// Paths containing `..`, absolute paths and other components that could escape the override directory are never
+/// looked up on disk and go straight to the fallback. Symlinks are followed only if their target is inside the override
+/// directory; others are treated as missing.
+///
+/// A file that shadows one in the fallback keeps the fallback's MIME type, so `mime(...)` overrides and `sniff` results
+/// from the derive macro still apply. Other files get a MIME type guessed from their extension.
+///
+/// Files are read with blocking I/O on the request path, so keep the override directory on a local disk.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+///
+/// use axum::Router;
+/// use axum_asset::{Asset, AssetService, AssetSource, FsOverride};
+///
+/// #[derive(Asset)]
+/// #[asset(dir = "tests/static")]
+/// struct StaticAssets;
+///
+/// let source: Arc<dyn AssetSource> = match std::env::var("ASSET_OVERRIDE_DIR") {
+///     Ok(dir) => Arc::new(FsOverride::new(dir, StaticAssets)),
+///     Err(_) => Arc::new(StaticAssets),
+/// };
+///
+/// let app: Router = Router::new().nest_service("/static", AssetService::new(source));
+/// ```
+pub struct FsOverride<S> {
+    dir: PathBuf,
+    fallback: S,
+    cache: Mutex<HashMap<String, CachedFile>>,
+}
+
+/// A file loaded from the override directory, with the metadata used to detect changes.
+struct CachedFile {
+    len: u64,
+    modified: SystemTime,
+    file: Arc<OwnedFile>,
+}
+
+impl<S> FsOverride<S> {
+    /// Create a source that serves files from `dir` before falling back to `fallback`.
+    pub fn new(dir: impl Into<PathBuf>, fallback: S) -> Self {
+        Self {
+            dir: dir.into(),
+            fallback,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Path of the override directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Resolve a request path to a file on disk, following symlinks.
+    ///
+    /// Returns `None` if the path is unsafe or resolves to a location outside the override directory, for example
+    /// through a symlink.
+    fn resolve(&self, path: &str) -> Option<PathBuf> {
+        if !is_safe_path(path) {
+            return None;
+        }
+
+        let dir = fs::canonicalize(&self.dir).ok()?;
+        let file_path = fs::canonicalize(self.dir.join(path)).ok()?;
+
+        file_path.starts_with(&dir).then_some(file_path)
+    }
+
+    /// Load a file from the override directory, using the cache if the file has not changed.
+    ///
+    /// A file shadowing one in the fallback takes its MIME type.
+    ///
+    /// The cache lock is only held to look up or store an entry, never while reading from disk.
+    fn load(&self, path: &str) -> Option<Arc<OwnedFile>>
+    where
+        S: AssetSource,
+    {
+        let file_path = self.resolve(path);
+        let metadata = file_path
+            .as_ref()
+            .and_then(|file_path| fs::metadata(file_path).ok())
+            .filter(|metadata| metadata.is_file());
+
+        let (Some(file_path), Some(metadata)) = (file_path, metadata) else {
+            self.cache().remove(path);
+            return None;
+        };
+
+        let len = metadata.len();
+        let modified = metadata.modified().unwrap_or(UNIX_EPOCH);
+
+        if let Some(cached) = self.cache().get(path)
+            && cached.len == len
+            && cached.modified == modified
+        {
+            return Some(cached.file.clone());
+        }
+
+        let contents = fs::read(&file_path).ok()?;
+        let last_modified = modified
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let file = match self.fallback.get(path) {
+            Some(shadowed) => OwnedFile::with_mime_type(
+                path.to_string(),
+                contents,
+                last_modified,
+                shadowed.mime_type().to_string(),
+            ),
+            None => OwnedFile::new(path.to_string(), contents, last_modified),
+        };
+        let file = Arc::new(file);
+
+        let mut cache = self.cache();
+
+        // Keep the cache small; evicting everything is fine since entries are cheap to reload
+        if cache.len() >= CACHE_CAPACITY && !cache.contains_key(path) {
+            cache.clear();
+        }
+        cache.insert(
+            path.to_string(),
+            CachedFile {
+                len,
+                modified,
+                file: file.clone(),
+            },
+        );
+
+        Some(file)
+    }
+
+    fn cache(&self) -> MutexGuard<'_, HashMap<String, CachedFile>> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<S: AssetSource> AssetSource for FsOverride<S> {
+    fn get(&self, path: &str) -> Option<AssetRef> {
+        match self.load(path) {
+            Some(file) => Some(AssetRef::from_owned(file)),
+            None => self.fallback.get(path),
+        }
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'static, str>> + '_> {
+        let mut paths: BTreeSet<_> = self.fallback.iter().collect();
+
+        for entry in WalkDir::new(&self.dir)
+            .follow_links(true)
+            .into_iter()
+            .flatten()
+        {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            if let Ok(relative_path) = entry.path().strip_prefix(&self.dir) {
+                let path = relative_path.to_string_lossy().replace('\\', "/");
+
+                if self.resolve(&path).is_some() {
+                    paths.insert(Cow::Owned(path));
+                }
+            }
+        }
+
+        Box::new(paths.into_iter())
+    }
+}
+
+impl<S> fmt::Debug for FsOverride<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FsOverride")
+            .field("dir", &self.dir)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Check that a request path stays inside the override directory.
+fn is_safe_path(path: &str) -> bool {
+    !path.is_empty()
+        && !path.contains('\\')
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
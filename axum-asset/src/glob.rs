@@ -0,0 +1,26 @@
+use std::{error::Error, fmt};
+
+use globset::{GlobBuilder, GlobMatcher};
+
+/// Error returned by [`Asset::glob`](crate::Asset::glob) for an invalid pattern.
+#[derive(Debug, Clone)]
+pub struct GlobError(globset::Error);
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for GlobError {}
+
+/// Compile a glob pattern for matching embedded paths.
+///
+/// `*` and `?` do not match `/`, so `*.css` only matches files in the root and `**/*.css` matches at any depth.
+pub(crate) fn matcher(pattern: &str) -> Result<GlobMatcher, GlobError> {
+    GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .map(|glob| glob.compile_matcher())
+        .map_err(GlobError)
+}
@@ -6,9 +6,13 @@
 //! # Features
 //!
 //! - **Compile-time embedding**: Files are read and embedded during compilation
-//! - **HTTP caching**: Automatic `ETag`, `Last-Modified`, and `Cache-Control` headers
+//! - **HTTP caching**: Automatic `ETag`, `Last-Modified`, and `Cache-Control` headers, with configurable `ETag`s
 //! - **Conditional requests**: Handles `If-None-Match` and `If-Modified-Since` with `304 Not Modified`
-//! - **MIME type detection**: Automatically determines content types from file extensions
+//! - **Range requests**: Serves single byte ranges with `206 Partial Content`, honoring `If-Range`
+//! - **Integrity digests**: Optional RFC 9530 `Repr-Digest` headers, honoring `Want-Repr-Digest`
+//! - **Content addressing**: Optional immutable route serving files by their SHA-256 hash
+//! - **Directory listings**: Optional HTML and JSON indexes of embedded directories
+//! - **MIME type detection**: Automatically determines content types from file extensions, with overrides
 //!
 //! # Quick Start
 //!
@@ -22,7 +26,7 @@
 //! struct StaticAssets;
 //!
 //! // Mount the assets on your router
-//! let app = Router::new().nest("/static", StaticAssets::router());
+//! let app: Router = Router::new().nest("/static", StaticAssets::router());
 //! ```
 //!
 //! With a directory structure like:
@@ -51,8 +55,163 @@
 //!
 //! ## Attributes
 //!
+//! Keys are separated by commas inside an `#[asset(...)]` attribute. Boolean keys can be written on their own as a
+//! shorthand for `= true`, for example `#[asset(dir = "static", sniff, strip_bom)]`.
+//!
 //! - `#[asset(dir = "path")]` - Required. Path to the directory containing assets, relative to the crate's
-//!   `Cargo.toml`.
+//!   `Cargo.toml`. Environment variables written as `$VAR` or `${VAR}` are expanded, so assets generated by a build
+//!   script can be embedded with `#[asset(dir = "$OUT_DIR/dist")]`. Write `$$` for a literal `$` in a path.
+//! - `#[asset(archive = "dist.zip")]` - Alternative to `dir`. Path to a `.zip`, `.tar`, `.tar.gz` or `.tgz` archive,
+//!   resolved like `dir`. Its entries are unpacked at compile time and embedded like files in a directory, using each
+//!   entry's timestamp for `Last-Modified`.
+//! - `prefix = "v2"` - Optional. Path prefix prepended to every file from the `dir` or `archive` in the same attribute, so
+//!   `dist/app.js` is served as `v2/app.js`. Leading and trailing slashes are ignored.
+//! - `strip = "build"` - Optional. Leading directory removed from the paths of files under it, applied before
+//!   `prefix`. With `#[asset(dir = "dist", strip = "build")]`, `dist/build/app.js` is served as `app.js`; files outside
+//!   `build` keep their paths. It is a compile error if the directory does not exist.
+//! - `mime(ext = "mjs", type = "text/javascript")` - Optional, repeatable. Overrides the MIME type of every file with
+//!   the given extension.
+//! - `mime(path = "well-known/apple-app-site-association", type = "application/json")` - Optional, repeatable.
+//!   Overrides the MIME type of a single file. Takes precedence over extension overrides.
+//! - `sniff = true` - Optional. Detects the MIME type from the file contents (PNG, GIF, JPEG, WebP, PDF, WASM and
+//!   gzip signatures, plus HTML and JSON heuristics) when the extension is missing or unknown. A compile-time warning
+//!   is emitted when a file's signature contradicts its extension, such as a `.png` that is actually a JPEG.
+//! - `strip_bom = true` - Optional. Removes a leading UTF-8 byte order mark from textual files before embedding.
+//! - `normalize_newlines = true` - Optional. Converts CRLF line endings to LF in textual files before hashing and
+//!   embedding, so checkouts with `core.autocrlf` produce the same `ETag`s and binaries as other platforms.
+//! - `integrity = "sha384 sha512"` - Optional. Space-separated list of algorithms (`sha256`, `sha384` or `sha512`) used
+//!   to compute [`EmbeddedFileMetadata::integrity`]. Defaults to `sha384`.
+//! - `etag_hash = "blake3"` - Optional. Hash algorithm (`sha256`, `blake3` or `xxh3`) used to compute
+//!   [`EmbeddedFileMetadata::etag`]. Defaults to `sha256`, reusing the content hash.
+//! - `dedup_note = true` - Optional. Emits a compile-time note reporting how many bytes were saved by deduplication.
+//! - `report = "asset-report.json"` - Optional. Writes a report listing every embedded file with its size, gzip size,
+//!   MIME type and hash, plus totals. The path is resolved like `dir`, so a relative path is relative to the crate
+//!   directory, not the workspace: in a workspace, `target/...` points into `<crate>/target`. Use an absolute path or
+//!   `$VAR` to write elsewhere. A `.md` extension produces Markdown and anything else produces JSON.
+//! - `max_file_size = 1048576` - Optional. Fails compilation if any file is larger than this many bytes.
+//! - `max_total_size = 10485760` - Optional. Fails compilation if the embedded bytes (after deduplication) exceed this
+//!   many bytes.
+//!
+//! Repeat the attribute to merge several directories and archives into one type. Each attribute may declare one `dir`
+//! or `archive` (with its own `prefix` and `strip`); every other key applies to the whole type and may only be given
+//! once. Two sources providing the same path is a compile error, as is a file whose path is also a directory of
+//! another file after `prefix` and `strip` are applied.
+//!
+//! ```rust,no_run
+//! use axum_asset::Asset;
+//!
+//! #[derive(Asset)]
+//! #[asset(dir = "tests/static")]
+//! #[asset(dir = "tests/duplicates", prefix = "gen")]
+//! struct SiteAssets;
+//!
+//! assert!(SiteAssets::get("gen/app.js").is_some());
+//! ```
+//!
+//! Files with identical contents (such as vendored or localized copies) are embedded once and share the same bytes,
+//! regardless of how many paths they appear under.
+//!
+//! Textual files (`text/*`, JavaScript, JSON, XML and their `+json`/`+xml` variants) are validated as UTF-8 at compile
+//! time and served with `; charset=utf-8` appended to their `Content-Type`. Files that fail validation produce a
+//! compile-time warning and are served without a charset.
+//!
+//! # Combining Assets
+//!
+//! Asset types can be composed with adapter types that implement [`Asset`] themselves, so [`Asset::router`] works on
+//! the composition:
+//!
+//! - [`Overlay<A, B>`] serves files from `A`, falling back to `B` for paths `A` does not provide.
+//! - [`Prefixed<A, P>`] serves files from `A` under the path prefix given by a [`Prefix`] type.
+//! - [`Filtered<A, F>`] serves only the files from `A` accepted by a [`Filter`] type.
+//!
+//! ```rust,no_run
+//! use axum::Router;
+//! use axum_asset::{Asset, Overlay};
+//!
+//! #[derive(Asset)]
+//! #[asset(dir = "tests/static")]
+//! struct BaseAssets;
+//!
+//! #[derive(Asset)]
+//! #[asset(dir = "tests/theme")]
+//! struct CustomerAssets;
+//!
+//! // Customer files shadow the base files with the same path
+//! type SiteAssets = Overlay<CustomerAssets, BaseAssets>;
+//!
+//! let app: Router = Router::new().nest("/static", SiteAssets::router());
+//! ```
+//!
+//! # Runtime Asset Sources
+//!
+//! [`Asset`] only has associated functions, so it cannot be used as a trait object. Every [`Asset`] type (including
+//! the combinators above, created with [`Default`]) also implements the object-safe [`AssetSource`] trait, which
+//! returns [`AssetRef`] values. An `Arc<dyn AssetSource>` can be chosen at startup and served with [`AssetService`]:
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//!
+//! use axum::Router;
+//! use axum_asset::{Asset, AssetService, AssetSource, Overlay};
+//!
+//! #[derive(Asset)]
+//! #[asset(dir = "tests/static")]
+//! struct BaseAssets;
+//!
+//! #[derive(Asset)]
+//! #[asset(dir = "tests/theme")]
+//! struct CustomerAssets;
+//!
+//! let customized = std::env::var("CUSTOMER").is_ok();
+//!
+//! let source: Arc<dyn AssetSource> = if customized {
+//!     Arc::new(Overlay::<CustomerAssets, BaseAssets>::default())
+//! } else {
+//!     Arc::new(BaseAssets)
+//! };
+//!
+//! let app: Router = Router::new().nest_service("/static", AssetService::new(source));
+//! ```
+//!
+//! [`FsOverride`] layers a directory on disk over another source, so operators can patch individual files in
+//! production without rebuilding the binary. With the `archive` feature enabled, `ArchiveSource` serves the files of a
+//! zip or tar archive loaded at startup.
+//!
+//! # Serving Files From Handlers
+//!
+//! [`EmbeddedFile`] implements [`IntoResponse`](axum::response::IntoResponse), so handlers can return a file picked at
+//! runtime. Extract [`Conditional`] and call [`EmbeddedFile::respond_to`] to also answer conditional and range requests
+//! like the router does:
+//!
+//! ```rust,no_run
+//! use axum::{Router, response::Response, routing::get};
+//! use axum_asset::{Asset, Conditional};
+//!
+//! #[derive(Asset)]
+//! #[asset(dir = "tests/static")]
+//! struct StaticAssets;
+//!
+//! async fn index(conditional: Conditional) -> Response {
+//!     StaticAssets::get("index.html").unwrap().respond_to(&conditional)
+//! }
+//!
+//! let app: Router = Router::new()
+//!     .route("/", get(index))
+//!     .nest("/static", StaticAssets::router());
+//! ```
+//!
+//! # Exporting Assets
+//!
+//! [`Asset::extract_to`] writes every embedded file to a directory with its modification time set to
+//! `last_modified`, for example to push the exact embedded bytes to a CDN origin during deploys.
+//! [`Asset::extract_with`] can also write a JSON manifest, and [`dump_assets_from_args`] adds a `--dump-assets <dir>`
+//! flag to a binary to inspect what actually shipped.
+//!
+//! # Generated Assets
+//!
+//! Assets produced by a build script (such as a compiled front-end bundle) can be embedded by pointing `dir` at
+//! `$OUT_DIR`. The companion `axum-asset-build` crate stages files into `OUT_DIR`, runs transforms on them and can emit
+//! the module containing the derived type directly.
 //!
 //! # Accessing Files Programmatically
 //!
@@ -69,17 +228,37 @@
 //! if let Some(file) = StaticAssets::get("index.html") {
 //!     println!("Content length: {}", file.metadata.size);
 //!     println!("MIME type: {}", file.metadata.mime_type);
-//!     println!("ETag: {}", file.metadata.content_hash);
+//!     println!("ETag: {}", file.metadata.etag);
+//!     println!("Integrity: {}", file.metadata.integrity);
+//!     println!("Digest: {}", file.metadata.content_digest_base64url());
 //! }
 //!
-//! // Iterate over all embedded file paths
-//! for path in StaticAssets::iter() {
-//!     println!("Embedded: {}", path);
+//! // Iterate over all embedded files
+//! for file in StaticAssets::files() {
+//!     println!("Embedded: {} ({} bytes)", file.path, file.metadata.size);
+//! }
+//! println!("Total size: {} bytes", StaticAssets::total_size());
+//!
+//! // Browse the directory tree, precomputed at compile time
+//! for entry in StaticAssets::read_dir("css/") {
+//!     println!("{} (directory: {})", entry.name(), entry.is_dir());
 //! }
 //! ```
 
+#[cfg(feature = "archive")]
+mod archive;
 mod asset;
+mod combinators;
+mod conditional;
+mod dir;
+mod extract;
 mod file;
+mod fs_override;
+mod glob;
+mod listing;
+mod options;
+mod service;
+mod source;
 mod util;
 
 /// Derive macro for implementing the [`Asset`] trait.
@@ -90,7 +269,25 @@ mod util;
 /// # Attributes
 ///
 /// - `#[asset(dir = "path")]` - Required. Path to the directory containing assets, relative to the crate's
-///   `Cargo.toml`.
+///   `Cargo.toml`. Environment variables written as `$VAR` or `${VAR}` are expanded; `$$` is a literal `$`.
+/// - `#[asset(archive = "path")]` - Alternative to `dir`. Path to a zip or tar archive unpacked at compile time.
+/// - `prefix = "path"` - Optional. Path prefix for files from the `dir` or `archive` in the same attribute.
+/// - `strip = "path"` - Optional. Leading directory removed from paths of files under it, before `prefix`.
+/// - `mime(ext = "ext", type = "type/subtype")` - Optional, repeatable. Overrides the MIME type for an extension.
+/// - `mime(path = "path", type = "type/subtype")` - Optional, repeatable. Overrides the MIME type for a single file.
+/// - `sniff = true` - Optional. Detects the MIME type from file contents when the extension is missing or unknown.
+/// - `strip_bom = true` - Optional. Removes a leading UTF-8 byte order mark from textual files.
+/// - `normalize_newlines = true` - Optional. Converts CRLF line endings to LF in textual files.
+/// - `integrity = "sha384"` - Optional. Space-separated algorithms used for Subresource Integrity metadata.
+/// - `etag_hash = "sha256"` - Optional. Hash algorithm (`sha256`, `blake3` or `xxh3`) used for `ETag` values.
+/// - `dedup_note = true` - Optional. Reports the bytes saved by embedding identical files only once.
+/// - `report = "path"` - Optional. Writes a JSON (or Markdown, for `.md`) report of embedded files and sizes. Relative
+///   paths are resolved against the crate directory, like `dir`.
+/// - `max_file_size = 1048576` - Optional. Per-file size budget in bytes, enforced at compile time.
+/// - `max_total_size = 10485760` - Optional. Total embedded size budget in bytes, enforced at compile time.
+///
+/// The attribute can be repeated to merge several sources, each with its own `dir` or `archive`, `prefix` and
+/// `strip`.
 ///
 /// # Example
 ///
@@ -106,7 +303,18 @@ mod util;
 /// ```
 pub use axum_asset_derive::Asset;
 
+#[cfg(feature = "archive")]
+pub use self::archive::ArchiveSource;
 pub use self::{
     asset::Asset,
+    combinators::{Filter, Filtered, Overlay, Prefix, Prefixed},
+    conditional::Conditional,
+    dir::DirEntry,
+    extract::{ExtractOptions, dump_assets_from, dump_assets_from_args},
     file::{EmbeddedFile, EmbeddedFileMetadata},
+    fs_override::FsOverride,
+    glob::GlobError,
+    options::{EtagStrategy, ServeOptions},
+    service::AssetService,
+    source::{AssetRef, AssetSource},
 };
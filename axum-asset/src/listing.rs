@@ -0,0 +1,224 @@
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use axum::{
+    http::{HeaderMap, HeaderValue, header::ACCEPT, header::CONTENT_TYPE, header::VARY},
+    response::{IntoResponse, Response},
+};
+use axum_extra::{TypedHeader, headers::CacheControl};
+
+use crate::{Asset, DirEntry};
+
+/// Collect the entries of every embedded directory, including the root (`""`).
+///
+/// Entries are sorted with subdirectories first, then by name.
+pub fn directories<A: Asset + ?Sized>() -> BTreeMap<&'static str, Vec<DirEntry>> {
+    let mut directories = BTreeMap::new();
+    let mut pending = vec![""];
+
+    while let Some(dir) = pending.pop() {
+        let mut entries: Vec<_> = A::read_dir(dir).collect();
+        entries.sort_by_key(|entry| (entry.is_file(), entry.name()));
+
+        pending.extend(
+            entries
+                .iter()
+                .filter(|entry| entry.is_dir())
+                .map(DirEntry::path),
+        );
+        directories.insert(dir, entries);
+    }
+
+    directories
+}
+
+/// Render a directory listing as HTML, or as JSON if the request accepts `application/json`.
+///
+/// `request_path` is the path the client requested. Links are relative to it, so the listing works regardless of
+/// where the router is mounted.
+pub fn respond(
+    headers: &HeaderMap,
+    request_path: &str,
+    dir: &str,
+    entries: &[DirEntry],
+) -> Response {
+    // Without a trailing slash, relative links resolve against the parent, so prefix them with the directory name.
+    // Links always start with `./`, so a first segment containing `:` is never read as a URL scheme.
+    let base = match request_path.ends_with('/') {
+        true => "./".to_string(),
+        false => format!("./{}/", request_path.rsplit('/').next().unwrap_or_default()),
+    };
+
+    let (content_type, body) = if wants_json(headers) {
+        ("application/json", json_listing(dir, entries))
+    } else {
+        (
+            "text/html; charset=utf-8",
+            html_listing(dir, &base, entries),
+        )
+    };
+
+    (
+        [
+            (CONTENT_TYPE, HeaderValue::from_static(content_type)),
+            (VARY, HeaderValue::from_static("accept")),
+        ],
+        TypedHeader(CacheControl::new().with_no_cache()),
+        body,
+    )
+        .into_response()
+}
+
+/// Check if the client prefers a JSON listing, based on the quality values in the `Accept` header.
+///
+/// JSON must be listed explicitly with a non-zero quality and at least the quality of HTML. HTML quality comes from the
+/// most specific of `text/html`, `text/*` and `*/*`.
+fn wants_json(headers: &HeaderMap) -> bool {
+    let mut json = 0.0;
+    let mut html = None;
+
+    for media_range in headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+    {
+        let mut params = media_range.split(';');
+        let media_type = params.next().unwrap_or_default().trim();
+        let quality = params
+            .filter_map(|param| param.trim().split_once('='))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+            .and_then(|(_, value)| value.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+
+        let specificity = match media_type.to_ascii_lowercase().as_str() {
+            "application/json" => {
+                json = f32::max(json, quality);
+                continue;
+            }
+            "text/html" => 3,
+            "text/*" => 2,
+            "*/*" => 1,
+            _ => continue,
+        };
+
+        if html.is_none_or(|(current, _)| specificity > current) {
+            html = Some((specificity, quality));
+        }
+    }
+
+    let html = html.map_or(0.0, |(_, quality)| quality);
+    json > 0.0 && json >= html
+}
+
+fn json_listing(dir: &str, entries: &[DirEntry]) -> String {
+    let mut json = format!("{{\"path\":{},\"entries\":[", crate::util::json_string(dir));
+
+    for (index, entry) in entries.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+
+        // Writing to a `String` never fails
+        let _ = match entry {
+            DirEntry::File(file) => write!(
+                json,
+                "{{\"name\":{},\"type\":\"file\",\"path\":{},\"size\":{},\"mime_type\":{},\"last_modified\":{}}}",
+                crate::util::json_string(entry.name()),
+                crate::util::json_string(file.path),
+                file.metadata.size,
+                crate::util::json_string(file.metadata.mime_type),
+                file.metadata.last_modified,
+            ),
+            DirEntry::Dir(_) => write!(
+                json,
+                "{{\"name\":{},\"type\":\"directory\"}}",
+                crate::util::json_string(entry.name()),
+            ),
+        };
+    }
+
+    json.push_str("]}");
+    json
+}
+
+fn html_listing(dir: &str, base: &str, entries: &[DirEntry]) -> String {
+    let title = html_escape(&format!("Index of /{dir}"));
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<table>\n<tr><th>Name</th><th>Size</th><th>Type</th><th>Last modified</th></tr>\n"
+    );
+
+    // Writing to a `String` never fails
+    if !dir.is_empty() {
+        let _ = writeln!(
+            html,
+            "<tr><td><a href=\"{}../\">../</a></td><td></td><td></td><td></td></tr>",
+            html_escape(base)
+        );
+    }
+
+    for entry in entries {
+        let name = html_escape(entry.name());
+        let href = html_escape(&format!("{base}{}", crate::util::encode_path(entry.name())));
+
+        let _ = match entry {
+            DirEntry::File(file) => writeln!(
+                html,
+                "<tr><td><a href=\"{href}\">{name}</a></td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                file.metadata.size,
+                html_escape(file.metadata.mime_type),
+                format_timestamp(file.metadata.last_modified),
+            ),
+            DirEntry::Dir(_) => writeln!(
+                html,
+                "<tr><td><a href=\"{href}/\">{name}/</a></td><td></td><td></td><td></td></tr>"
+            ),
+        };
+    }
+
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+/// Escape text for use in HTML content and attribute values.
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Format a Unix timestamp as `YYYY-MM-DD HH:MM:SS UTC`.
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+
+    // Civil date from days since 1970-01-01, from Howard Hinnant's `civil_from_days`
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    )
+}
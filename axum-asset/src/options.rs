@@ -0,0 +1,163 @@
+/// Options controlling how embedded files are served.
+///
+/// Pass these to [`Asset::router_with`](crate::Asset::router_with). The defaults match [`Asset::router`](crate::Asset::router).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::Router;
+/// use axum_asset::{Asset, EtagStrategy, ServeOptions};
+///
+/// #[derive(Asset)]
+/// #[asset(dir = "tests/static")]
+/// struct StaticAssets;
+///
+/// let options = ServeOptions::new()
+///     .with_repr_digest()
+///     .with_etag(EtagStrategy::Truncated { len: 16, weak: false });
+/// let app: Router = Router::new().nest("/static", StaticAssets::router_with(options));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ServeOptions {
+    repr_digest: bool,
+    etag: EtagStrategy,
+    content_route: Option<String>,
+    directory_listing: bool,
+}
+
+impl ServeOptions {
+    /// Create options with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send an RFC 9530 `Repr-Digest: sha-256=:<base64>:` header with every successful response.
+    ///
+    /// Without this option, `Repr-Digest` is only sent when the request asks for a SHA-256 digest with
+    /// `Want-Repr-Digest`.
+    ///
+    /// Files are always served without a content coding, so the representation digest also covers the response body
+    /// and no separate `Content-Digest` header is sent.
+    pub fn with_repr_digest(mut self) -> Self {
+        self.repr_digest = true;
+        self
+    }
+
+    /// Set the strategy used to generate `ETag` headers.
+    pub fn with_etag(mut self, etag: EtagStrategy) -> Self {
+        self.etag = etag;
+        self
+    }
+
+    /// Serve every file by its SHA-256 content hash at `<prefix>/<content_hash>`, such as
+    /// `/_content/<content_hash>`.
+    ///
+    /// Responses from this route carry `Cache-Control: public, max-age=31536000, immutable` because the URL changes
+    /// whenever the contents do. Files are shared with the path routes, so nothing is embedded twice.
+    ///
+    /// The prefix is relative to where the router is mounted. A missing leading `/` is added and trailing slashes are
+    /// removed, so `"_content"`, `"/_content"` and `"/_content/"` are equivalent.
+    ///
+    /// Files with identical contents share a hash, so only one of them is served here: the first in the order of
+    /// [`Asset::iter`](crate::Asset::iter), with its own MIME type. The others are still served at their paths.
+    ///
+    /// Only [`Asset::router_with`](crate::Asset::router_with) serves this route;
+    /// [`AssetService`](crate::AssetService) ignores it.
+    pub fn with_content_route(mut self, prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        let prefix = prefix.trim_matches('/');
+        self.content_route = Some(match prefix.is_empty() {
+            true => String::new(),
+            false => format!("/{prefix}"),
+        });
+        self
+    }
+
+    /// Serve an index of the files and subdirectories of every embedded directory, including the root.
+    ///
+    /// Listings are rendered as HTML, or as JSON when the request's `Accept` header includes `application/json`. Each
+    /// file is listed with its size, MIME type and modification time. Listings are disabled by default.
+    ///
+    /// Subdirectories are listed both with and without a trailing slash. When the router is nested, the root listing
+    /// is served at the mount path itself, such as `/static`.
+    ///
+    /// Only [`Asset::router_with`](crate::Asset::router_with) serves listings;
+    /// [`AssetService`](crate::AssetService) ignores this option.
+    pub fn with_directory_listing(mut self) -> Self {
+        self.directory_listing = true;
+        self
+    }
+
+    /// Whether `Repr-Digest` is sent with every successful response.
+    pub fn repr_digest(&self) -> bool {
+        self.repr_digest
+    }
+
+    /// Strategy used to generate `ETag` headers.
+    pub fn etag(&self) -> EtagStrategy {
+        self.etag
+    }
+
+    /// Prefix of the content-addressed route, if enabled.
+    pub fn content_route(&self) -> Option<&str> {
+        self.content_route.as_deref()
+    }
+
+    /// Whether directory listings are served.
+    pub fn directory_listing(&self) -> bool {
+        self.directory_listing
+    }
+}
+
+/// How `ETag` headers are generated from [`EmbeddedFileMetadata::etag`](crate::EmbeddedFileMetadata::etag).
+///
+/// The hash itself is computed at compile time; its algorithm can be changed with `#[asset(etag_hash = "...")]`. Files
+/// are always served without a content coding, so each file has exactly one `ETag`.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum EtagStrategy {
+    /// A strong `ETag` containing the full hash, such as `"<hash>"`.
+    #[default]
+    Strong,
+
+    /// A weak `ETag` containing the full hash, such as `W/"<hash>"`.
+    Weak,
+
+    /// An `ETag` containing only the first `len` characters of the hash.
+    ///
+    /// `len` is raised to at least [`EtagStrategy::MIN_TRUNCATED_LEN`], so that a tiny value cannot give many files
+    /// the same `ETag`.
+    Truncated {
+        /// Number of hash characters to keep.
+        len: usize,
+
+        /// Whether the `ETag` is weak.
+        weak: bool,
+    },
+
+    /// A custom function that receives the hash and returns the complete header value, including quotes and the
+    /// optional `W/` prefix. If the returned value is not a valid `ETag`, the header is omitted.
+    Custom(fn(&str) -> String),
+}
+
+impl EtagStrategy {
+    /// Fewest hash characters kept by [`EtagStrategy::Truncated`].
+    pub const MIN_TRUNCATED_LEN: usize = 8;
+
+    /// Generate the `ETag` header value for a hash.
+    pub fn header_value(&self, hash: &str) -> String {
+        match *self {
+            Self::Strong => format!("\"{hash}\""),
+            Self::Weak => format!("W/\"{hash}\""),
+            Self::Truncated { len, weak } => {
+                let hash = hash.get(..len.max(Self::MIN_TRUNCATED_LEN)).unwrap_or(hash);
+
+                if weak {
+                    format!("W/\"{hash}\"")
+                } else {
+                    format!("\"{hash}\"")
+                }
+            }
+            Self::Custom(etag) => etag(hash),
+        }
+    }
+}
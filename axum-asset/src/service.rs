@@ -0,0 +1,124 @@
+use std::{
+    convert::Infallible,
+    fmt,
+    future::{Ready, ready},
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{
+    body::Body,
+    http::{HeaderValue, Method, Request, StatusCode, header::ALLOW},
+    response::{IntoResponse, Response},
+};
+use tower_service::Service;
+
+use crate::{AssetSource, Conditional, ServeOptions};
+
+/// A [`Service`] that serves files from a runtime [`AssetSource`].
+///
+/// The request path is percent-decoded and looked up in the source without its leading slash, so `/my%20file.txt`
+/// serves `my file.txt`. `GET` and `HEAD` requests for known files
+/// are answered with the same caching headers and conditional request handling as [`Asset::router`]; unknown paths
+/// get `404 Not Found` and other methods get `405 Method Not Allowed`.
+///
+/// [`ServeOptions::with_content_route`] and [`ServeOptions::with_directory_listing`] do not apply: both are built from
+/// the embedded files when the router is created, so content routes and listings are only served by
+/// [`Asset::router_with`]. Directory paths get `404 Not Found`. The `ETag` and `Repr-Digest` options are honored.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+///
+/// use axum::Router;
+/// use axum_asset::{Asset, AssetService, AssetSource};
+///
+/// #[derive(Asset)]
+/// #[asset(dir = "tests/static")]
+/// struct StaticAssets;
+///
+/// let source: Arc<dyn AssetSource> = Arc::new(StaticAssets);
+/// let app: Router = Router::new().nest_service("/static", AssetService::new(source));
+/// ```
+///
+/// [`Asset::router`]: crate::Asset::router
+/// [`Asset::router_with`]: crate::Asset::router_with
+#[derive(Clone)]
+pub struct AssetService {
+    source: Arc<dyn AssetSource>,
+    options: Arc<ServeOptions>,
+}
+
+impl AssetService {
+    /// Create a service serving files from `source` with default [`ServeOptions`].
+    pub fn new(source: Arc<dyn AssetSource>) -> Self {
+        Self {
+            source,
+            options: Arc::new(ServeOptions::default()),
+        }
+    }
+
+    /// Use the given [`ServeOptions`] for all responses.
+    ///
+    /// [`ServeOptions::with_content_route`] and [`ServeOptions::with_directory_listing`] are ignored; see the
+    /// [type documentation](Self).
+    pub fn with_options(mut self, options: ServeOptions) -> Self {
+        self.options = Arc::new(options);
+        self
+    }
+
+    fn respond<B>(&self, request: &Request<B>) -> Response {
+        let method = request.method();
+
+        if method != Method::GET && method != Method::HEAD {
+            return (
+                StatusCode::METHOD_NOT_ALLOWED,
+                [(ALLOW, HeaderValue::from_static("GET, HEAD"))],
+            )
+                .into_response();
+        }
+
+        let Some(path) = crate::util::decode_path(request.uri().path()) else {
+            return StatusCode::NOT_FOUND.into_response();
+        };
+        let Some(file) = self.source.get(path.trim_start_matches('/')) else {
+            return StatusCode::NOT_FOUND.into_response();
+        };
+
+        let mut response = crate::util::respond(
+            &Conditional::from_headers(request.headers()),
+            &file,
+            &self.options,
+            crate::util::cache_control(),
+        );
+
+        if method == Method::HEAD {
+            *response.body_mut() = Body::empty();
+        }
+
+        response
+    }
+}
+
+impl fmt::Debug for AssetService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssetService")
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> Service<Request<B>> for AssetService {
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Ready<Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        ready(Ok(self.respond(&request)))
+    }
+}
@@ -0,0 +1,234 @@
+use std::{borrow::Cow, ops, sync::Arc};
+
+use axum::body::{Body, Bytes};
+use sha2::{Digest, Sha256};
+
+use crate::{Asset, EmbeddedFile};
+
+/// Object-safe access to a set of assets.
+///
+/// Unlike [`Asset`], which only has associated functions, `AssetSource` takes `&self`, so asset sets can be chosen at
+/// runtime and stored as `Arc<dyn AssetSource>`. Every [`Asset`] type implements it, so a derived unit struct can be
+/// used directly as a source.
+///
+/// **Note**: With both traits in scope, `StaticAssets::get(path)` is ambiguous; write `<StaticAssets as Asset>::get`
+/// or call the methods on a `dyn AssetSource` value instead.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+///
+/// use axum_asset::{Asset, AssetSource};
+///
+/// #[derive(Asset)]
+/// #[asset(dir = "tests/static")]
+/// struct LightAssets;
+///
+/// #[derive(Asset)]
+/// #[asset(dir = "tests/theme")]
+/// struct DarkAssets;
+///
+/// let dark_mode = std::env::var("DARK_MODE").is_ok();
+///
+/// let source: Arc<dyn AssetSource> = if dark_mode {
+///     Arc::new(DarkAssets)
+/// } else {
+///     Arc::new(LightAssets)
+/// };
+///
+/// let file = source.get("style.css");
+/// ```
+pub trait AssetSource: Send + Sync {
+    /// Get a file by path.
+    ///
+    /// **Note**: The path should be relative to the asset root, without a leading slash.
+    fn get(&self, path: &str) -> Option<AssetRef>;
+
+    /// Iterate over the paths of all files.
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'static, str>> + '_>;
+}
+
+impl<T> AssetSource for T
+where
+    T: Asset + Send + Sync,
+{
+    fn get(&self, path: &str) -> Option<AssetRef> {
+        T::get(path).map(AssetRef::from)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'static, str>> + '_> {
+        Box::new(T::iter().map(Cow::Borrowed))
+    }
+}
+
+/// A file returned by an [`AssetSource`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetRef {
+    repr: Repr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Repr {
+    Embedded(EmbeddedFile),
+    Owned(Arc<OwnedFile>),
+}
+
+/// A file loaded at runtime, such as from an [`FsOverride`](crate::FsOverride) directory or an archive.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct OwnedFile {
+    pub path: String,
+    pub contents: Bytes,
+    pub content_hash: String,
+    pub content_digest: [u8; 32],
+    pub last_modified: u64,
+    pub mime_type: String,
+    pub charset: Option<&'static str>,
+}
+
+impl OwnedFile {
+    /// Compute metadata for a file loaded at runtime, matching what the derive macro computes at compile time.
+    pub fn new(path: String, contents: Vec<u8>, last_modified: u64) -> Self {
+        let mime_type = mime_guess::from_path(&path)
+            .first_or_octet_stream()
+            .to_string();
+
+        Self::with_mime_type(path, contents, last_modified, mime_type)
+    }
+
+    /// Compute metadata for a file loaded at runtime whose MIME type is already known.
+    pub fn with_mime_type(
+        path: String,
+        contents: Vec<u8>,
+        last_modified: u64,
+        mime_type: String,
+    ) -> Self {
+        let content_digest: [u8; 32] = Sha256::digest(&contents).into();
+        let charset = (axum_asset_common::is_text_mime_type(&mime_type)
+            && std::str::from_utf8(&contents).is_ok())
+        .then_some("utf-8");
+
+        Self {
+            path,
+            content_hash: hex::encode(content_digest),
+            content_digest,
+            last_modified,
+            mime_type,
+            charset,
+            contents: Bytes::from(contents),
+        }
+    }
+}
+
+impl AssetRef {
+    /// Path of the file relative to the asset root.
+    pub fn path(&self) -> &str {
+        match &self.repr {
+            Repr::Embedded(file) => file.path,
+            Repr::Owned(file) => &file.path,
+        }
+    }
+
+    /// Contents of the file.
+    pub fn contents(&self) -> &[u8] {
+        match &self.repr {
+            Repr::Embedded(file) => file.contents,
+            Repr::Owned(file) => &file.contents,
+        }
+    }
+
+    /// SHA-256 hash of the file contents (hex-encoded).
+    pub fn content_hash(&self) -> &str {
+        match &self.repr {
+            Repr::Embedded(file) => file.metadata.content_hash,
+            Repr::Owned(file) => &file.content_hash,
+        }
+    }
+
+    /// SHA-256 hash of the file contents (raw bytes).
+    pub fn content_digest(&self) -> [u8; 32] {
+        match &self.repr {
+            Repr::Embedded(file) => file.metadata.content_digest,
+            Repr::Owned(file) => file.content_digest,
+        }
+    }
+
+    /// Hash of the file contents used for the `ETag` header (hex-encoded).
+    pub fn etag(&self) -> &str {
+        match &self.repr {
+            Repr::Embedded(file) => file.metadata.etag,
+            Repr::Owned(file) => &file.content_hash,
+        }
+    }
+
+    /// Unix timestamp of last modification.
+    pub fn last_modified(&self) -> u64 {
+        match &self.repr {
+            Repr::Embedded(file) => file.metadata.last_modified,
+            Repr::Owned(file) => file.last_modified,
+        }
+    }
+
+    /// MIME type of the file.
+    pub fn mime_type(&self) -> &str {
+        match &self.repr {
+            Repr::Embedded(file) => file.metadata.mime_type,
+            Repr::Owned(file) => &file.mime_type,
+        }
+    }
+
+    /// Character encoding of textual files.
+    pub fn charset(&self) -> Option<&str> {
+        match &self.repr {
+            Repr::Embedded(file) => file.metadata.charset,
+            Repr::Owned(file) => file.charset,
+        }
+    }
+
+    /// File size in bytes.
+    pub fn size(&self) -> u64 {
+        match &self.repr {
+            Repr::Embedded(file) => file.metadata.size,
+            Repr::Owned(file) => file.contents.len() as u64,
+        }
+    }
+
+    /// The embedded file, if this file was embedded at compile time rather than loaded at runtime.
+    pub fn as_embedded(&self) -> Option<EmbeddedFile> {
+        match &self.repr {
+            Repr::Embedded(file) => Some(*file),
+            Repr::Owned(_) => None,
+        }
+    }
+
+    /// Wrap a file loaded at runtime.
+    pub(crate) fn from_owned(file: Arc<OwnedFile>) -> Self {
+        Self {
+            repr: Repr::Owned(file),
+        }
+    }
+
+    /// Response body containing the file contents.
+    pub(crate) fn body(&self) -> Body {
+        match &self.repr {
+            Repr::Embedded(file) => Body::from(file.contents),
+            Repr::Owned(file) => Body::from(file.contents.clone()),
+        }
+    }
+
+    /// Response body containing a byte range of the file contents.
+    pub(crate) fn body_range(&self, range: ops::Range<usize>) -> Body {
+        match &self.repr {
+            Repr::Embedded(file) => Body::from(&file.contents[range]),
+            Repr::Owned(file) => Body::from(file.contents.slice(range)),
+        }
+    }
+}
+
+impl From<EmbeddedFile> for AssetRef {
+    fn from(file: EmbeddedFile) -> Self {
+        Self {
+            repr: Repr::Embedded(file),
+        }
+    }
+}
@@ -1,101 +1,323 @@
 use std::{
+    fmt::Write as _,
+    ops::{Bound, RangeInclusive},
     str::FromStr,
     time::{Duration, UNIX_EPOCH},
 };
 
 use axum::{
-    http::StatusCode,
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
 };
 use axum_extra::{
     TypedHeader,
     headers::{
-        CacheControl, ContentLength, ContentType, ETag, IfModifiedSince, IfNoneMatch, LastModified,
+        AcceptRanges, CacheControl, ContentLength, ContentRange, ContentType, ETag, LastModified,
+        Range,
     },
 };
 
-use crate::EmbeddedFile;
+use base64::{Engine, prelude::BASE64_STANDARD};
 
-/// Generate an ETag header from a content hash.
-fn etag(embedded_file: EmbeddedFile) -> Option<TypedHeader<ETag>> {
-    let content_hash = embedded_file.metadata.content_hash;
-    Some(TypedHeader(
-        ETag::from_str(&format!("\"{content_hash}\"")).ok()?,
-    ))
+use crate::{AssetRef, Conditional, EtagStrategy, ServeOptions};
+
+/// `Repr-Digest` response header (RFC 9530).
+const REPR_DIGEST: HeaderName = HeaderName::from_static("repr-digest");
+
+/// `Want-Repr-Digest` request header (RFC 9530).
+const WANT_REPR_DIGEST: HeaderName = HeaderName::from_static("want-repr-digest");
+
+/// Generate an ETag header from the file's ETag hash.
+fn etag(file: &AssetRef, strategy: EtagStrategy) -> Option<TypedHeader<ETag>> {
+    let etag = strategy.header_value(file.etag());
+    Some(TypedHeader(ETag::from_str(&etag).ok()?))
 }
 
 /// Generate a Last-Modified header from a timestamp.
-fn last_modified(embedded_file: EmbeddedFile) -> TypedHeader<LastModified> {
-    let last_modified = UNIX_EPOCH + Duration::from_secs(embedded_file.metadata.last_modified);
+fn last_modified(file: &AssetRef) -> TypedHeader<LastModified> {
+    let last_modified = UNIX_EPOCH + Duration::from_secs(file.last_modified());
     TypedHeader(LastModified::from(last_modified))
 }
 
-/// Generate a Content-Type header from a file extension.
-fn content_type(embedded_file: EmbeddedFile) -> Option<TypedHeader<ContentType>> {
-    Some(TypedHeader(
-        ContentType::from_str(embedded_file.metadata.mime_type).ok()?,
-    ))
+/// Generate a Content-Type header from the MIME type and charset.
+///
+/// A charset already in the MIME type, such as one set with `mime(...)`, is kept and the detected one is not appended.
+fn content_type(file: &AssetRef) -> Option<TypedHeader<ContentType>> {
+    let mime_type = file.mime_type();
+    let has_charset = mime_type
+        .parse::<mime_guess::Mime>()
+        .is_ok_and(|mime| mime.get_param(mime_guess::mime::CHARSET).is_some());
+
+    let content_type = match file.charset() {
+        Some(charset) if !has_charset => {
+            ContentType::from_str(&format!("{mime_type}; charset={charset}"))
+        }
+        _ => ContentType::from_str(mime_type),
+    };
+
+    Some(TypedHeader(content_type.ok()?))
 }
 
 /// Generate a Cache-Control header with no-cache and public directives.
-fn cache_control() -> TypedHeader<CacheControl> {
-    TypedHeader(CacheControl::new().with_no_cache().with_public())
+pub fn cache_control() -> CacheControl {
+    CacheControl::new().with_no_cache().with_public()
+}
+
+/// Generate a Cache-Control header for content that never changes at its URL.
+pub fn immutable_cache_control() -> CacheControl {
+    CacheControl::new()
+        .with_public()
+        .with_max_age(Duration::from_secs(31_536_000))
+        .with_immutable()
 }
 
-fn content_length(embedded_file: EmbeddedFile) -> TypedHeader<ContentLength> {
-    TypedHeader(ContentLength(embedded_file.metadata.size))
+fn content_length(file: &AssetRef) -> TypedHeader<ContentLength> {
+    TypedHeader(ContentLength(file.size()))
+}
+
+/// Generate a Repr-Digest header from the SHA-256 content digest.
+fn repr_digest(file: &AssetRef) -> Option<[(HeaderName, HeaderValue); 1]> {
+    let digest = BASE64_STANDARD.encode(file.content_digest());
+    let value = HeaderValue::from_str(&format!("sha-256=:{digest}:")).ok()?;
+
+    Some([(REPR_DIGEST, value)])
+}
+
+/// Check whether a `Want-Repr-Digest` request header asks for a SHA-256 digest.
+///
+/// The header is a structured field dictionary mapping algorithms to preferences from 0 to 10, where 0 means "not
+/// acceptable", for example `sha-256=1, sha-512=3`.
+pub fn wants_repr_digest(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(WANT_REPR_DIGEST)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|member| {
+            let (key, preference) = member.split_once('=').unwrap_or((member, "1"));
+            let preference = preference.split(';').next().unwrap_or_default().trim();
+
+            key.trim().eq_ignore_ascii_case("sha-256")
+                && (preference == "?1" || preference.parse::<u8>().is_ok_and(|p| p > 0))
+        })
 }
 
 /// Generate a Not-Modified response with appropriate headers.
-fn not_modified_response(embedded_file: EmbeddedFile) -> Response {
+fn not_modified_response(
+    file: &AssetRef,
+    etag_strategy: EtagStrategy,
+    cache_control: CacheControl,
+) -> Response {
     (
         StatusCode::NOT_MODIFIED,
-        etag(embedded_file),
-        last_modified(embedded_file),
-        cache_control(),
+        etag(file, etag_strategy),
+        last_modified(file),
+        TypedHeader(cache_control),
     )
         .into_response()
 }
 
 /// Generate an OK response with appropriate headers.
-fn ok_response(embedded_file: EmbeddedFile) -> Response {
+fn ok_response(
+    file: &AssetRef,
+    etag_strategy: EtagStrategy,
+    cache_control: CacheControl,
+    with_repr_digest: bool,
+) -> Response {
     (
         StatusCode::OK,
-        etag(embedded_file),
-        last_modified(embedded_file),
-        cache_control(),
-        content_type(embedded_file),
-        content_length(embedded_file),
-        embedded_file.contents,
+        etag(file, etag_strategy),
+        last_modified(file),
+        TypedHeader(cache_control),
+        content_type(file),
+        content_length(file),
+        TypedHeader(AcceptRanges::bytes()),
+        with_repr_digest.then(|| repr_digest(file)).flatten(),
+        file.body(),
+    )
+        .into_response()
+}
+
+/// Generate a Partial-Content response for an inclusive byte range of the file.
+fn partial_content_response(
+    file: &AssetRef,
+    range: RangeInclusive<u64>,
+    etag_strategy: EtagStrategy,
+    cache_control: CacheControl,
+    with_repr_digest: bool,
+) -> Response {
+    let Ok(content_range) = ContentRange::bytes(range.clone(), file.size()) else {
+        return ok_response(file, etag_strategy, cache_control, with_repr_digest);
+    };
+
+    (
+        StatusCode::PARTIAL_CONTENT,
+        etag(file, etag_strategy),
+        last_modified(file),
+        TypedHeader(cache_control),
+        content_type(file),
+        TypedHeader(ContentLength(range.end() - range.start() + 1)),
+        TypedHeader(content_range),
+        TypedHeader(AcceptRanges::bytes()),
+        with_repr_digest.then(|| repr_digest(file)).flatten(),
+        file.body_range(*range.start() as usize..*range.end() as usize + 1),
     )
         .into_response()
 }
 
-/// Generate a response with appropriate headers based on the request headers.
+/// Generate a Range-Not-Satisfiable response.
+fn range_not_satisfiable_response(file: &AssetRef) -> Response {
+    (
+        StatusCode::RANGE_NOT_SATISFIABLE,
+        TypedHeader(ContentRange::unsatisfied_bytes(file.size())),
+        TypedHeader(AcceptRanges::bytes()),
+    )
+        .into_response()
+}
+
+/// Resolve the ranges of a `Range` header against a file of `len` bytes.
+///
+/// Returns the inclusive byte ranges that overlap the file; an empty result means the header cannot be satisfied.
+fn satisfiable_ranges(range: &Range, len: u64) -> Vec<RangeInclusive<u64>> {
+    range
+        .satisfiable_ranges(len)
+        .filter_map(|(start, end)| {
+            let start = match start {
+                Bound::Included(start) => start,
+                Bound::Excluded(start) => start.checked_add(1)?,
+                Bound::Unbounded => 0,
+            };
+            let end = match end {
+                Bound::Included(end) => end.min(len.checked_sub(1)?),
+                Bound::Excluded(end) => end.min(len).checked_sub(1)?,
+                Bound::Unbounded => len.checked_sub(1)?,
+            };
+
+            (start <= end).then_some(start..=end)
+        })
+        .collect()
+}
+
+/// Generate a response with appropriate headers based on the conditional and range request headers.
 pub fn respond(
-    if_none_match: Option<TypedHeader<IfNoneMatch>>,
-    if_modified_since: Option<TypedHeader<IfModifiedSince>>,
-    embedded_file: EmbeddedFile,
+    conditional: &Conditional,
+    file: &AssetRef,
+    options: &ServeOptions,
+    cache_control: CacheControl,
 ) -> Response {
-    let etag = etag(embedded_file);
-
-    match (if_none_match, if_modified_since) {
-        (Some(TypedHeader(if_none_match)), _) => {
-            if etag.is_some() && if_none_match.precondition_passes(&etag.unwrap().0) {
-                ok_response(embedded_file)
-            } else {
-                not_modified_response(embedded_file)
+    let etag_strategy = options.etag();
+    let etag = etag(file, etag_strategy);
+    let last_modified = last_modified(file);
+    let with_repr_digest = options.repr_digest() || conditional.wants_repr_digest();
+
+    let modified = match (conditional.if_none_match(), conditional.if_modified_since()) {
+        (Some(if_none_match), _) => etag
+            .as_ref()
+            .is_some_and(|etag| if_none_match.precondition_passes(&etag.0)),
+        (None, Some(if_modified_since)) => {
+            if_modified_since.is_modified(UNIX_EPOCH + Duration::from_secs(file.last_modified()))
+        }
+        (None, None) => true,
+    };
+
+    if !modified {
+        return not_modified_response(file, etag_strategy, cache_control);
+    }
+
+    // A stale `If-Range` means the client's partial copy is outdated, so it gets the whole file instead
+    let range = conditional.range().filter(|_| {
+        conditional.if_range().is_none_or(|if_range| {
+            !if_range.is_modified(etag.as_ref().map(|etag| &etag.0), Some(&last_modified.0))
+        })
+    });
+
+    let Some(range) = range else {
+        return ok_response(file, etag_strategy, cache_control, with_repr_digest);
+    };
+
+    // Multiple ranges would need a `multipart/byteranges` body; serving the whole file is always allowed instead
+    match satisfiable_ranges(range, file.size()).as_slice() {
+        [] => range_not_satisfiable_response(file),
+        [range] => partial_content_response(
+            file,
+            range.clone(),
+            etag_strategy,
+            cache_control,
+            with_repr_digest,
+        ),
+        _ => ok_response(file, etag_strategy, cache_control, with_repr_digest),
+    }
+}
+
+/// Percent-encode a path for use in a URL, leaving `/` separators intact.
+///
+/// Only characters that may not appear in a URL path are encoded, so paths such as `a(1).txt` keep the form browsers
+/// send while `#`, `?`, `%`, spaces and non-ASCII characters are escaped.
+pub fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => encoded.push(char::from(byte)),
+            b'-' | b'.' | b'_' | b'~' | b'/' | b':' | b'@' => encoded.push(char::from(byte)),
+            b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'=' => {
+                encoded.push(char::from(byte))
+            }
+            // Writing to a `String` never fails
+            _ => {
+                let _ = write!(encoded, "%{byte:02X}");
             }
         }
-        (None, Some(TypedHeader(if_modified_since))) => {
-            if if_modified_since
-                .is_modified(UNIX_EPOCH + Duration::from_secs(embedded_file.metadata.last_modified))
-            {
-                ok_response(embedded_file)
-            } else {
-                not_modified_response(embedded_file)
+    }
+
+    encoded
+}
+
+/// Percent-decode a URL path, the inverse of [`encode_path`].
+///
+/// Returns `None` when an escape is malformed or the decoded bytes are not valid UTF-8, since no embedded path can
+/// match such a request.
+pub fn decode_path(path: &str) -> Option<String> {
+    if !path.contains('%') {
+        return Some(path.to_string());
+    }
+
+    let mut bytes = path.bytes();
+    let mut decoded = Vec::with_capacity(path.len());
+
+    while let Some(byte) = bytes.next() {
+        if byte != b'%' {
+            decoded.push(byte);
+            continue;
+        }
+
+        let high = char::from(bytes.next()?).to_digit(16)?;
+        let low = char::from(bytes.next()?).to_digit(16)?;
+        decoded.push((high * 16 + low) as u8);
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+/// Encode a string as a JSON string literal.
+pub fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
             }
+            c => json.push(c),
         }
-        (None, None) => ok_response(embedded_file),
     }
+
+    json.push('"');
+    json
 }
@@ -0,0 +1,163 @@
+mod common;
+
+use axum::http::StatusCode;
+use axum_asset::Asset;
+
+use self::common::{get, get_body, get_header, get_status, router};
+
+/// Modification time of every entry in the test archives (2024-01-02T03:04:06Z).
+const ARCHIVE_MTIME: u64 = 1_704_164_646;
+
+const PATHS: &[&str] = &["assets/app.css", "assets/app.js", "index.html"];
+
+#[derive(Asset)]
+#[asset(archive = "tests/archive/dist.zip")]
+struct ZipAssets;
+
+#[derive(Asset)]
+#[asset(archive = "tests/archive/dist.tar.gz")]
+struct TarAssets;
+
+#[derive(Asset)]
+#[asset(archive = "tests/archive/dist.zip", strip = "assets", prefix = "v2")]
+#[asset(dir = "tests/static", prefix = "static")]
+struct MixedAssets;
+
+#[test]
+fn test_embedded_archive() {
+    assert_eq!(ZipAssets::iter().collect::<Vec<_>>(), PATHS);
+    assert_eq!(TarAssets::iter().collect::<Vec<_>>(), PATHS);
+
+    for path in PATHS {
+        let zip_file = ZipAssets::get(path).unwrap();
+        let tar_file = TarAssets::get(path).unwrap();
+
+        assert_eq!(zip_file.contents, tar_file.contents);
+        assert_eq!(
+            zip_file.metadata.content_hash,
+            tar_file.metadata.content_hash
+        );
+        assert_eq!(zip_file.metadata.last_modified, ARCHIVE_MTIME);
+        assert_eq!(tar_file.metadata.last_modified, ARCHIVE_MTIME);
+    }
+
+    let file = ZipAssets::get("assets/app.js").unwrap();
+    assert_eq!(file.contents, b"console.log(\"Hello from the archive\");\n");
+    assert_eq!(file.metadata.mime_type, "text/javascript");
+    assert_eq!(file.metadata.charset, Some("utf-8"));
+}
+
+#[test]
+fn test_embedded_archive_with_directories() {
+    assert_eq!(
+        MixedAssets::get("v2/app.js").unwrap().contents,
+        ZipAssets::get("assets/app.js").unwrap().contents
+    );
+    assert!(MixedAssets::get("v2/index.html").is_some());
+    assert!(MixedAssets::get("static/index.html").is_some());
+}
+
+#[tokio::test]
+async fn test_embedded_archive_router() {
+    let app = router::<ZipAssets>();
+
+    let response = get(app, "/static/index.html").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(
+        get_header(&response, "last-modified").unwrap(),
+        "Tue, 02 Jan 2024 03:04:06 GMT"
+    );
+    assert_eq!(
+        get_body(response).await,
+        ZipAssets::get("index.html").unwrap().contents
+    );
+}
+
+#[cfg(feature = "archive")]
+mod runtime {
+    use std::{io::Cursor, sync::Arc};
+
+    use axum::http::StatusCode;
+    use axum_asset::{ArchiveSource, Asset};
+
+    use super::{ARCHIVE_MTIME, PATHS, ZipAssets};
+    use crate::common::{get, get_body, get_header, get_status, service_router};
+
+    #[test]
+    fn test_archive_source() {
+        let sources = [
+            ArchiveSource::open("tests/archive/dist.zip").unwrap(),
+            ArchiveSource::open("tests/archive/dist.tar.gz").unwrap(),
+            ArchiveSource::from_zip(Cursor::new(include_bytes!("archive/dist.zip"))).unwrap(),
+        ];
+
+        for source in sources {
+            let source: Arc<dyn axum_asset::AssetSource> = Arc::new(source);
+            assert_eq!(source.iter().collect::<Vec<_>>(), PATHS);
+
+            for path in PATHS {
+                let file = source.get(path).unwrap();
+                let embedded = ZipAssets::get(path).unwrap();
+
+                assert!(file.as_embedded().is_none());
+                assert_eq!(file.contents(), embedded.contents);
+                assert_eq!(file.content_hash(), embedded.metadata.content_hash);
+                assert_eq!(file.mime_type(), embedded.metadata.mime_type);
+                assert_eq!(file.charset(), embedded.metadata.charset);
+                assert_eq!(file.last_modified(), ARCHIVE_MTIME);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unsupported_archive() {
+        assert!(ArchiveSource::open("tests/static/index.html").is_err());
+    }
+
+    #[test]
+    fn test_oversized_entry_header() {
+        // A tar entry whose header claims an exabyte of data (base-256 encoded) but holds a few bytes
+        let mut header = [0u8; 512];
+        header[..7].copy_from_slice(b"big.bin");
+        header[100..107].copy_from_slice(b"0000644");
+        header[124] = 0x80;
+        header[128..136].copy_from_slice(&(1u64 << 60).to_be_bytes());
+        header[136..147].copy_from_slice(b"00000000000");
+        header[156] = b'0';
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+
+        header[148..156].fill(b' ');
+        let checksum: u32 = header.iter().map(|&byte| u32::from(byte)).sum();
+        header[148..155].copy_from_slice(format!("{checksum:06o}\0").as_bytes());
+
+        let mut archive = header.to_vec();
+        archive.extend_from_slice(&[b'x'; 512]);
+
+        // Must not try to reserve the claimed size up front
+        if let Ok(source) = ArchiveSource::from_tar(Cursor::new(archive)) {
+            let source: Arc<dyn axum_asset::AssetSource> = Arc::new(source);
+            assert!(source.get("big.bin").unwrap().size() <= 512);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archive_service() {
+        let source = ArchiveSource::open("tests/archive/dist.tar.gz").unwrap();
+        let app = service_router(Arc::new(source));
+
+        let response = get(app.clone(), "/static/assets/app.css").await;
+        assert_eq!(get_status(&response), StatusCode::OK);
+        assert_eq!(
+            get_header(&response, "etag").unwrap(),
+            format!(
+                "\"{}\"",
+                ZipAssets::get("assets/app.css").unwrap().metadata.etag
+            )
+        );
+        assert_eq!(get_body(response).await, "body { margin: 0; }\n");
+
+        let response = get(app, "/static/missing.txt").await;
+        assert_eq!(get_status(&response), StatusCode::NOT_FOUND);
+    }
+}
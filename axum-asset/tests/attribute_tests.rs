@@ -0,0 +1,307 @@
+use axum_asset::Asset;
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+struct StaticAssets;
+
+#[derive(Asset)]
+#[asset(dir = "$CARGO_MANIFEST_DIR/tests/static")]
+struct EnvAssets;
+
+#[derive(Asset)]
+#[asset(dir = "${CARGO_MANIFEST_DIR}/tests/static")]
+struct BracedEnvAssets;
+
+#[derive(Asset)]
+#[asset(
+    dir = "tests/static",
+    mime(ext = "js", type = "text/javascript"),
+    mime(ext = "TXT", type = "text/x-custom"),
+    mime(path = "no-extension", type = "text/plain"),
+    mime(path = "nested/deep/file.txt", type = "text/markdown")
+)]
+struct MimeOverrideAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/sniff", sniff = true)]
+struct SniffedAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/sniff", sniff, mime(path = "data", type = "text/plain"))]
+struct SniffedOverrideAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/encoding")]
+struct BomAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/encoding", strip_bom = true)]
+struct StrippedBomAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/encoding", normalize_newlines = true)]
+struct NormalizedAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/static", integrity = "sha384 sha512")]
+struct IntegrityAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/duplicates")]
+struct DuplicateAssets;
+
+#[derive(Asset)]
+#[asset(
+    dir = "tests/duplicates",
+    report = "$CARGO_TARGET_TMPDIR/asset-report.json",
+    max_file_size = 1024,
+    max_total_size = 4096
+)]
+struct JsonReportAssets;
+
+#[derive(Asset)]
+#[asset(
+    dir = "tests/duplicates",
+    report = "$CARGO_TARGET_TMPDIR/asset-report.md"
+)]
+struct MarkdownReportAssets;
+
+#[derive(Asset)]
+#[asset(
+    dir = "tests/static",
+    mime(path = "gen/app.js", type = "text/x-generated")
+)]
+#[asset(dir = "tests/duplicates", prefix = "gen")]
+#[asset(dir = "tests/sniff", prefix = "/sniffed/")]
+struct MergedAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/static", strip = "nested/", prefix = "v2/")]
+struct RemappedAssets;
+
+#[test]
+fn test_env_expansion_in_dir() {
+    assert_eq!(EnvAssets::len(), StaticAssets::len());
+    assert_eq!(BracedEnvAssets::len(), StaticAssets::len());
+
+    for path in StaticAssets::iter() {
+        assert_eq!(EnvAssets::get(path), StaticAssets::get(path));
+        assert_eq!(BracedEnvAssets::get(path), StaticAssets::get(path));
+    }
+}
+
+#[test]
+fn test_mime_overrides() {
+    let mime_type = |path| MimeOverrideAssets::get(path).unwrap().metadata.mime_type;
+
+    assert_eq!(mime_type("script.js"), "text/javascript");
+    assert_eq!(mime_type("empty.txt"), "text/x-custom");
+    assert_eq!(mime_type("no-extension"), "text/plain");
+    assert_eq!(mime_type("nested/deep/file.txt"), "text/markdown");
+    assert_eq!(mime_type("style.css"), "text/css");
+
+    assert_eq!(
+        StaticAssets::get("no-extension")
+            .unwrap()
+            .metadata
+            .mime_type,
+        "application/octet-stream"
+    );
+}
+
+#[test]
+fn test_sniffed_mime_types() {
+    let mime_type = |path| SniffedAssets::get(path).unwrap().metadata.mime_type;
+
+    assert_eq!(mime_type("image"), "image/png");
+    assert_eq!(mime_type("page"), "text/html");
+    assert_eq!(mime_type("data"), "application/json");
+    assert_eq!(mime_type("notes"), "application/octet-stream");
+    assert_eq!(mime_type("webp"), "image/webp");
+
+    // `WEBP` at offset 8 is only a WebP image inside a RIFF container
+    assert_eq!(mime_type("not-webp"), "application/octet-stream");
+
+    // Explicit overrides take precedence over sniffing
+    assert_eq!(
+        SniffedOverrideAssets::get("data")
+            .unwrap()
+            .metadata
+            .mime_type,
+        "text/plain"
+    );
+}
+
+#[test]
+fn test_charset_and_bom() {
+    let file = BomAssets::get("bom.txt").unwrap();
+    assert_eq!(file.metadata.charset, Some("utf-8"));
+    assert!(file.contents.starts_with(b"\xef\xbb\xbf"));
+
+    let stripped = StrippedBomAssets::get("bom.txt").unwrap();
+    assert_eq!(stripped.metadata.charset, Some("utf-8"));
+    assert_eq!(stripped.contents, &file.contents[3..]);
+    assert_eq!(stripped.metadata.size, file.metadata.size - 3);
+    assert_ne!(stripped.metadata.content_hash, file.metadata.content_hash);
+
+    assert_eq!(
+        StaticAssets::get("index.html").unwrap().metadata.charset,
+        Some("utf-8")
+    );
+    assert_eq!(
+        StaticAssets::get("data.json").unwrap().metadata.charset,
+        Some("utf-8")
+    );
+    assert_eq!(
+        StaticAssets::get("no-extension").unwrap().metadata.charset,
+        None
+    );
+    assert_eq!(SniffedAssets::get("image").unwrap().metadata.charset, None);
+}
+
+#[test]
+fn test_normalize_newlines() {
+    let file = NormalizedAssets::get("crlf.txt").unwrap();
+    assert_eq!(file.contents, b"line one\nline two\n");
+    assert_eq!(file.metadata.size, file.contents.len() as u64);
+
+    // Byte order marks are left alone unless `strip_bom` is set
+    assert_eq!(
+        NormalizedAssets::get("bom.txt").unwrap().contents,
+        BomAssets::get("bom.txt").unwrap().contents
+    );
+}
+
+#[test]
+fn test_integrity() {
+    // SHA-384 of an empty input
+    assert_eq!(
+        StaticAssets::get("empty.txt").unwrap().metadata.integrity,
+        "sha384-OLBgp1GsljhM2TJ+sbHjaiH9txEUvgdDTAzHv2P24donTt6/529l+9Ua0vFImLlb"
+    );
+
+    for path in StaticAssets::iter() {
+        let integrity = StaticAssets::get(path).unwrap().metadata.integrity;
+        assert!(integrity.starts_with("sha384-"));
+        assert_eq!(integrity.len(), "sha384-".len() + 64);
+
+        let integrity = IntegrityAssets::get(path).unwrap().metadata.integrity;
+        let (sha384, sha512) = integrity.split_once(' ').unwrap();
+        assert_eq!(sha384, StaticAssets::get(path).unwrap().metadata.integrity);
+        assert!(sha512.starts_with("sha512-"));
+        assert_eq!(sha512.len(), "sha512-".len() + 88);
+    }
+}
+
+#[test]
+fn test_deduplicated_contents() {
+    let lib = DuplicateAssets::get("lib.js").unwrap();
+    let copy = DuplicateAssets::get("lib.copy.js").unwrap();
+    let vendored = DuplicateAssets::get("vendor/lib.js").unwrap();
+    let app = DuplicateAssets::get("app.js").unwrap();
+
+    assert_eq!(lib.contents, copy.contents);
+    assert_eq!(lib.metadata.content_hash, vendored.metadata.content_hash);
+
+    // Identical contents share a single static
+    assert_eq!(lib.contents.as_ptr(), copy.contents.as_ptr());
+    assert_eq!(lib.contents.as_ptr(), vendored.contents.as_ptr());
+    assert_ne!(lib.contents.as_ptr(), app.contents.as_ptr());
+
+    // Paths and metadata stay per file
+    assert_eq!(vendored.path, "vendor/lib.js");
+    assert_eq!(copy.path, "lib.copy.js");
+}
+
+#[test]
+fn test_json_report() {
+    let report =
+        std::fs::read_to_string(concat!(env!("CARGO_TARGET_TMPDIR"), "/asset-report.json"))
+            .unwrap();
+    let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), JsonReportAssets::len());
+
+    for entry in files {
+        let file = JsonReportAssets::get(entry["path"].as_str().unwrap()).unwrap();
+
+        assert_eq!(entry["size"], file.metadata.size);
+        assert_eq!(entry["mime_type"], file.metadata.mime_type);
+        assert_eq!(entry["content_hash"], file.metadata.content_hash);
+        assert!(entry["gzip_size"].as_u64().unwrap() > 0);
+    }
+
+    let size: u64 = JsonReportAssets::iter()
+        .map(|path| JsonReportAssets::get(path).unwrap().metadata.size)
+        .sum();
+    let duplicate_size = JsonReportAssets::get("lib.js").unwrap().metadata.size * 2;
+
+    let totals = &report["totals"];
+    assert_eq!(totals["files"], JsonReportAssets::len());
+    assert_eq!(totals["size"], size);
+    assert_eq!(totals["saved_bytes"], duplicate_size);
+    assert_eq!(totals["embedded_size"], size - duplicate_size);
+}
+
+#[test]
+fn test_markdown_report() {
+    let report =
+        std::fs::read_to_string(concat!(env!("CARGO_TARGET_TMPDIR"), "/asset-report.md")).unwrap();
+
+    assert!(report.starts_with("# Embedded assets"));
+    for path in MarkdownReportAssets::iter() {
+        assert!(report.contains(&format!("| `{path}` |")));
+    }
+}
+
+#[test]
+fn test_merged_directories() {
+    assert_eq!(
+        MergedAssets::len(),
+        StaticAssets::len() + DuplicateAssets::len() + SniffedAssets::len()
+    );
+
+    for path in StaticAssets::iter() {
+        assert_eq!(MergedAssets::get(path), StaticAssets::get(path));
+    }
+
+    for path in DuplicateAssets::iter() {
+        let file = MergedAssets::get(&format!("gen/{path}")).unwrap();
+        let original = DuplicateAssets::get(path).unwrap();
+
+        assert_eq!(file.path, format!("gen/{path}"));
+        assert_eq!(file.contents, original.contents);
+        assert!(MergedAssets::get(path).is_none());
+    }
+
+    assert!(MergedAssets::get("sniffed/data").is_some());
+
+    // Paths in MIME overrides include the prefix
+    assert_eq!(
+        MergedAssets::get("gen/app.js").unwrap().metadata.mime_type,
+        "text/x-generated"
+    );
+
+    let paths: Vec<_> = MergedAssets::iter().collect();
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(paths, sorted);
+}
+
+#[test]
+fn test_strip_and_prefix() {
+    assert_eq!(RemappedAssets::len(), StaticAssets::len());
+
+    let file = RemappedAssets::get("v2/deep/file.txt").unwrap();
+    assert_eq!(file.path, "v2/deep/file.txt");
+    assert_eq!(
+        file.contents,
+        StaticAssets::get("nested/deep/file.txt").unwrap().contents
+    );
+
+    // Files outside the stripped directory only get the prefix
+    assert!(RemappedAssets::get("v2/index.html").is_some());
+    assert!(RemappedAssets::get("v2/nested/deep/file.txt").is_none());
+    assert!(RemappedAssets::iter().all(|path| path.starts_with("v2/")));
+}
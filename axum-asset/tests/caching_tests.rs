@@ -5,7 +5,7 @@ use axum_asset::Asset;
 
 use crate::common::get_with_headers;
 
-use self::common::{get, get_body, get_header, get_status, router};
+use self::common::{content_type, get, get_body, get_header, get_status, router};
 
 #[derive(Asset)]
 #[asset(dir = "tests/static")]
@@ -61,8 +61,8 @@ async fn test_file_with_incorrect_if_none_match(path: &str) {
         Some(format!("\"{}\"", file.metadata.content_hash))
     );
     assert_eq!(
-        get_header(&response, "content-type").as_deref(),
-        Some(file.metadata.mime_type)
+        get_header(&response, "content-type"),
+        Some(content_type(file))
     );
     assert!(get_header(&response, "last-modified").is_some());
     assert_eq!(
@@ -128,8 +128,8 @@ async fn test_file_with_incorrect_if_modified_since(path: &str) {
         Some(format!("\"{}\"", file.metadata.content_hash))
     );
     assert_eq!(
-        get_header(&response, "content-type").as_deref(),
-        Some(file.metadata.mime_type)
+        get_header(&response, "content-type"),
+        Some(content_type(file))
     );
     assert!(get_header(&response, "last-modified").is_some());
     assert_eq!(
@@ -205,8 +205,8 @@ async fn test_file_with_incorrect_if_none_match_and_if_modified_since(path: &str
         Some(format!("\"{}\"", file.metadata.content_hash))
     );
     assert_eq!(
-        get_header(&cached_response, "content-type").as_deref(),
-        Some(file.metadata.mime_type)
+        get_header(&cached_response, "content-type"),
+        Some(content_type(file))
     );
     assert!(get_header(&cached_response, "last-modified").is_some());
     assert_eq!(
@@ -279,8 +279,8 @@ async fn test_file_with_incorrect_if_none_match_and_incorrect_if_modified_since(
         Some(format!("\"{}\"", file.metadata.content_hash))
     );
     assert_eq!(
-        get_header(&response, "content-type").as_deref(),
-        Some(file.metadata.mime_type)
+        get_header(&response, "content-type"),
+        Some(content_type(file))
     );
     assert!(get_header(&response, "last-modified").is_some());
     assert_eq!(
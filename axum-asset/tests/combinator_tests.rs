@@ -0,0 +1,126 @@
+mod common;
+
+use axum::http::StatusCode;
+use axum_asset::{Asset, Filter, Filtered, Overlay, Prefix, Prefixed};
+
+use self::common::{get, get_body, get_status, router};
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+struct StaticAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/theme")]
+struct ThemeAssets;
+
+type ThemedAssets = Overlay<ThemeAssets, StaticAssets>;
+
+struct V2;
+
+impl Prefix for V2 {
+    const PREFIX: &'static str = "/v2/";
+}
+
+type VersionedAssets = Prefixed<StaticAssets, V2>;
+
+struct Scripts;
+
+impl Filter for Scripts {
+    fn matches(path: &str) -> bool {
+        path.ends_with(".js")
+    }
+}
+
+type ScriptAssets = Filtered<StaticAssets, Scripts>;
+
+#[test]
+fn test_overlay() {
+    // `style.css` is shadowed and `logo.svg` is added
+    assert_eq!(ThemedAssets::len(), StaticAssets::len() + 1);
+    assert_eq!(ThemedAssets::iter().count(), ThemedAssets::len());
+
+    assert_eq!(
+        ThemedAssets::get("style.css"),
+        ThemeAssets::get("style.css")
+    );
+    assert_eq!(ThemedAssets::get("logo.svg"), ThemeAssets::get("logo.svg"));
+    assert_eq!(
+        ThemedAssets::get("index.html"),
+        StaticAssets::get("index.html")
+    );
+    assert!(ThemedAssets::get("missing.txt").is_none());
+}
+
+#[test]
+fn test_prefixed() {
+    assert_eq!(VersionedAssets::len(), StaticAssets::len());
+
+    for (path, inner_path) in VersionedAssets::iter().zip(StaticAssets::iter()) {
+        assert_eq!(path, format!("v2/{inner_path}"));
+
+        let file = VersionedAssets::get(path).unwrap();
+        assert_eq!(file.path, path);
+        assert_eq!(
+            file.contents,
+            StaticAssets::get(inner_path).unwrap().contents
+        );
+    }
+
+    assert!(VersionedAssets::get("index.html").is_none());
+    assert!(VersionedAssets::get("v2index.html").is_none());
+}
+
+#[test]
+fn test_prefixed_paths_are_shared() {
+    struct V3;
+
+    impl Prefix for V3 {
+        const PREFIX: &'static str = "v3";
+    }
+
+    // Each prefix gets its own table, and repeated lookups return the same path instead of allocating
+    let v2 = VersionedAssets::get("v2/index.html").unwrap().path;
+    let v3 = Prefixed::<StaticAssets, V3>::get("v3/index.html")
+        .unwrap()
+        .path;
+    assert_eq!(v2, "v2/index.html");
+    assert_eq!(v3, "v3/index.html");
+
+    assert!(std::ptr::eq(
+        v2,
+        VersionedAssets::get("v2/index.html").unwrap().path
+    ));
+    assert!(std::ptr::eq(
+        v2,
+        VersionedAssets::files()
+            .find(|file| file.path == v2)
+            .unwrap()
+            .path
+    ));
+}
+
+#[test]
+fn test_filtered() {
+    assert_eq!(ScriptAssets::iter().collect::<Vec<_>>(), ["script.js"]);
+    assert_eq!(ScriptAssets::len(), 1);
+    assert!(ScriptAssets::get("script.js").is_some());
+    assert!(ScriptAssets::get("index.html").is_none());
+}
+
+#[tokio::test]
+async fn test_router_on_composition() {
+    let app = router::<Prefixed<ThemedAssets, V2>>();
+
+    let response = get(app.clone(), "/static/v2/style.css").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(
+        get_body(response).await,
+        ThemeAssets::get("style.css").unwrap().contents
+    );
+
+    let response = get(app.clone(), "/static/v2/index.html").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+
+    let response = get(app, "/static/style.css").await;
+    assert_eq!(get_status(&response), StatusCode::NOT_FOUND);
+}
@@ -4,14 +4,27 @@ use axum::{
     http::{Request, StatusCode},
     response::Response,
 };
-use axum_asset::Asset;
+use std::sync::Arc;
+
+use axum_asset::{Asset, AssetService, AssetSource, EmbeddedFile, ServeOptions};
 use http_body_util::BodyExt;
 use tower::ServiceExt;
 
+#[allow(unused)]
 pub fn router<A: Asset>() -> Router {
     Router::new().nest("/static", A::router())
 }
 
+#[allow(unused)]
+pub fn router_with<A: Asset>(options: ServeOptions) -> Router {
+    Router::new().nest("/static", A::router_with(options))
+}
+
+#[allow(unused)]
+pub fn service_router(source: Arc<dyn AssetSource>) -> Router {
+    Router::new().nest_service("/static", AssetService::new(source))
+}
+
 pub async fn get(app: Router, uri: &str) -> Response {
     app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
         .await
@@ -29,10 +42,12 @@ pub async fn get_with_headers(app: Router, uri: &str, headers: Vec<(&str, &str)>
         .unwrap()
 }
 
+#[allow(unused)]
 pub fn get_status(response: &Response) -> StatusCode {
     response.status()
 }
 
+#[allow(unused)]
 pub fn get_header(response: &Response, header_name: &str) -> Option<String> {
     response
         .headers()
@@ -40,9 +55,18 @@ pub fn get_header(response: &Response, header_name: &str) -> Option<String> {
         .map(|value| value.to_str().unwrap().to_string())
 }
 
+#[allow(unused)]
 pub async fn get_body(response: Response) -> Bytes {
     let body = response.into_body();
     let body = body.collect().await.unwrap();
 
     body.to_bytes()
 }
+
+#[allow(unused)]
+pub fn content_type(file: EmbeddedFile) -> String {
+    match file.metadata.charset {
+        Some(charset) => format!("{}; charset={charset}", file.metadata.mime_type),
+        None => file.metadata.mime_type.to_string(),
+    }
+}
@@ -0,0 +1,7 @@
+use axum_asset::Asset;
+
+#[derive(Asset)]
+#[asset(dir = "tests/static", sniff = true, sniff = false)]
+struct StaticAssets;
+
+fn main() {}
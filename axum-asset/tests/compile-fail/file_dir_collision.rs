@@ -0,0 +1,8 @@
+use axum_asset::Asset;
+
+#[derive(Asset)]
+#[asset(dir = "$AXUM_ASSET_TESTS/static")]
+#[asset(dir = "$AXUM_ASSET_TESTS/theme", prefix = "index.html")]
+struct MergedAssets;
+
+fn main() {}
@@ -0,0 +1,7 @@
+use axum_asset::Asset;
+
+#[derive(Asset)]
+#[asset(dir = "$AXUM_ASSET_TESTS/theme", max_file_size = 95)]
+struct ThemeAssets;
+
+fn main() {}
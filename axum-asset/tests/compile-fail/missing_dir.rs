@@ -0,0 +1,7 @@
+use axum_asset::Asset;
+
+#[derive(Asset)]
+#[asset(sniff = true)]
+struct StaticAssets;
+
+fn main() {}
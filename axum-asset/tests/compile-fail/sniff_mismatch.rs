@@ -0,0 +1,9 @@
+#![deny(deprecated)]
+
+use axum_asset::Asset;
+
+#[derive(Asset)]
+#[asset(dir = "$AXUM_ASSET_TESTS/mismatch", sniff)]
+struct MismatchedAssets;
+
+fn main() {}
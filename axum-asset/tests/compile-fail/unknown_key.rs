@@ -0,0 +1,7 @@
+use axum_asset::Asset;
+
+#[derive(Asset)]
+#[asset(dir = "tests/static", prefx = "v2")]
+struct StaticAssets;
+
+fn main() {}
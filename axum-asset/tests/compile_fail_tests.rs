@@ -0,0 +1,15 @@
+#[test]
+fn test_compile_fail() {
+    // trybuild compiles each case in a scratch crate, so relative `dir` paths would resolve there; cases reach the
+    // fixtures through `$AXUM_ASSET_TESTS` instead, which the compiler inherits from this process
+    // SAFETY: this is the only test in the binary, so no other thread reads the environment concurrently
+    unsafe {
+        std::env::set_var(
+            "AXUM_ASSET_TESTS",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests"),
+        );
+    }
+
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile-fail/*.rs");
+}
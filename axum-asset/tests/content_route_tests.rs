@@ -0,0 +1,142 @@
+mod common;
+
+use axum::{Router, http::StatusCode};
+use axum_asset::{Asset, ServeOptions};
+
+use self::common::{
+    content_type, get, get_body, get_header, get_status, get_with_headers, router_with,
+};
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+struct StaticAssets;
+
+#[derive(Asset)]
+#[asset(
+    dir = "tests/duplicates",
+    mime(path = "lib.copy.js", type = "text/x-duplicate")
+)]
+struct DuplicateAssets;
+
+fn router_with_content_route() -> Router {
+    router_with::<StaticAssets>(ServeOptions::new().with_content_route("/_content/"))
+}
+
+#[tokio::test]
+async fn test_content_route() {
+    let app = router_with_content_route();
+
+    for path in StaticAssets::iter() {
+        let file = StaticAssets::get(path).unwrap();
+
+        let response = get(
+            app.clone(),
+            &format!("/static/_content/{}", file.metadata.content_hash),
+        )
+        .await;
+
+        assert_eq!(get_status(&response), StatusCode::OK);
+        assert_eq!(
+            get_header(&response, "cache-control").as_deref(),
+            Some("public, immutable, max-age=31536000")
+        );
+        assert_eq!(
+            get_header(&response, "etag"),
+            Some(format!("\"{}\"", file.metadata.etag))
+        );
+
+        let body = get_body(response).await;
+        assert_eq!(body, file.contents);
+
+        // Path routes keep their revalidation policy
+        let response = get(app.clone(), &format!("/static/{path}")).await;
+        assert_eq!(
+            get_header(&response, "cache-control").as_deref(),
+            Some("no-cache, public")
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_content_route_conditional_request() {
+    let app = router_with_content_route();
+    let file = StaticAssets::get("style.css").unwrap();
+    let uri = format!("/static/_content/{}", file.metadata.content_hash);
+
+    let response = get_with_headers(
+        app,
+        &uri,
+        vec![("if-none-match", &format!("\"{}\"", file.metadata.etag))],
+    )
+    .await;
+
+    assert_eq!(get_status(&response), StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn test_content_route_unknown_hash() {
+    let app = router_with_content_route();
+
+    let response = get(app, &format!("/static/_content/{}", "0".repeat(64))).await;
+    assert_eq!(get_status(&response), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_content_route_disabled_by_default() {
+    let app = common::router::<StaticAssets>();
+    let file = StaticAssets::get("style.css").unwrap();
+
+    let response = get(
+        app,
+        &format!("/static/_content/{}", file.metadata.content_hash),
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_content_route_prefix_is_normalized() {
+    let file = StaticAssets::get("style.css").unwrap();
+
+    for prefix in ["_content", "/_content", "_content//", ""] {
+        let options = ServeOptions::new().with_content_route(prefix);
+        let app = router_with::<StaticAssets>(options);
+
+        let prefix = prefix.trim_matches('/');
+        let uri = match prefix.is_empty() {
+            true => format!("/static/{}", file.metadata.content_hash),
+            false => format!("/static/{prefix}/{}", file.metadata.content_hash),
+        };
+
+        let response = get(app, &uri).await;
+        assert_eq!(get_status(&response), StatusCode::OK, "{uri}");
+    }
+}
+
+#[tokio::test]
+async fn test_content_route_identical_files() {
+    let app = router_with::<DuplicateAssets>(ServeOptions::new().with_content_route("/_content"));
+    let copy = DuplicateAssets::get("lib.copy.js").unwrap();
+    let original = DuplicateAssets::get("lib.js").unwrap();
+    assert_eq!(copy.metadata.content_hash, original.metadata.content_hash);
+    assert_ne!(copy.metadata.mime_type, original.metadata.mime_type);
+
+    // `lib.copy.js` comes first, so it is served with its own MIME type
+    let response = get(
+        app.clone(),
+        &format!("/static/_content/{}", copy.metadata.content_hash),
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(
+        get_header(&response, "content-type"),
+        Some(content_type(copy))
+    );
+
+    // Both stay available at their paths
+    let response = get(app, "/static/lib.js").await;
+    assert_eq!(
+        get_header(&response, "content-type"),
+        Some(content_type(original))
+    );
+}
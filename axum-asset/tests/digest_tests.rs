@@ -0,0 +1,114 @@
+mod common;
+
+use axum::Router;
+use axum_asset::{Asset, ServeOptions};
+use base64::{
+    Engine,
+    prelude::{BASE64_STANDARD, BASE64_URL_SAFE_NO_PAD},
+};
+
+use self::common::{get, get_header, get_with_headers, router, router_with};
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+struct StaticAssets;
+
+fn expected_repr_digest(path: &str) -> String {
+    let file = StaticAssets::get(path).unwrap();
+
+    format!("sha-256=:{}:", file.metadata.content_digest_base64())
+}
+
+fn router_with_repr_digest() -> Router {
+    router_with::<StaticAssets>(ServeOptions::new().with_repr_digest())
+}
+
+#[tokio::test]
+async fn test_repr_digest_disabled_by_default() {
+    let response = get(router::<StaticAssets>(), "/static/index.html").await;
+
+    assert_eq!(get_header(&response, "repr-digest"), None);
+}
+
+#[tokio::test]
+async fn test_repr_digest_enabled() {
+    let app = router_with_repr_digest();
+
+    for path in StaticAssets::iter() {
+        let response = get(app.clone(), &format!("/static/{path}")).await;
+
+        assert_eq!(
+            get_header(&response, "repr-digest"),
+            Some(expected_repr_digest(path))
+        );
+    }
+
+    let response = get(app, "/static/empty.txt").await;
+    assert_eq!(
+        get_header(&response, "repr-digest").as_deref(),
+        Some("sha-256=:47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=:")
+    );
+}
+
+#[tokio::test]
+async fn test_want_repr_digest() {
+    let app = router::<StaticAssets>();
+
+    let response = get_with_headers(
+        app.clone(),
+        "/static/index.html",
+        vec![("want-repr-digest", "sha-512=3, sha-256=1")],
+    )
+    .await;
+    assert_eq!(
+        get_header(&response, "repr-digest"),
+        Some(expected_repr_digest("index.html"))
+    );
+
+    let response = get_with_headers(
+        app.clone(),
+        "/static/index.html",
+        vec![("want-repr-digest", "sha-256=0")],
+    )
+    .await;
+    assert_eq!(get_header(&response, "repr-digest"), None);
+
+    let response = get_with_headers(
+        app,
+        "/static/index.html",
+        vec![("want-repr-digest", "sha-512=10")],
+    )
+    .await;
+    assert_eq!(get_header(&response, "repr-digest"), None);
+}
+
+#[test]
+fn test_content_digest() {
+    for path in StaticAssets::iter() {
+        let metadata = StaticAssets::get(path).unwrap().metadata;
+
+        assert_eq!(hex::encode(metadata.content_digest), metadata.content_hash);
+        assert_eq!(
+            BASE64_STANDARD
+                .decode(metadata.content_digest_base64())
+                .unwrap(),
+            metadata.content_digest
+        );
+        assert_eq!(
+            BASE64_URL_SAFE_NO_PAD
+                .decode(metadata.content_digest_base64url())
+                .unwrap(),
+            metadata.content_digest
+        );
+    }
+
+    let metadata = StaticAssets::get("empty.txt").unwrap().metadata;
+    assert_eq!(
+        metadata.content_digest_base64(),
+        "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+    );
+    assert_eq!(
+        metadata.content_digest_base64url(),
+        "47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU"
+    );
+}
@@ -0,0 +1,154 @@
+use axum_asset::{Asset, DirEntry, Filter, Filtered, Overlay};
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+struct StaticAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+#[asset(dir = "tests/duplicates", prefix = "lib/v1")]
+struct MergedAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/theme")]
+struct ThemeAssets;
+
+struct All;
+
+impl Filter for All {
+    fn matches(_path: &str) -> bool {
+        true
+    }
+}
+
+/// Uses the default `read_dir`, `walk` and `exists_dir` implementations built on `iter`.
+type DefaultImpl<A> = Filtered<A, All>;
+
+fn read_dir<A: Asset>(path: &str) -> Vec<DirEntry> {
+    A::read_dir(path).collect()
+}
+
+fn walk<A: Asset>(prefix: &str) -> Vec<&'static str> {
+    A::walk(prefix).map(|file| file.path).collect()
+}
+
+#[test]
+fn test_read_dir() {
+    let entries = read_dir::<StaticAssets>("");
+    let paths: Vec<_> = entries.iter().map(DirEntry::path).collect();
+    assert_eq!(
+        paths,
+        [
+            "data.json",
+            "empty.txt",
+            "index.html",
+            "nested",
+            "no-extension",
+            "script.js",
+            "style.css"
+        ]
+    );
+
+    let nested = entries.iter().find(|entry| entry.is_dir()).unwrap();
+    assert_eq!(*nested, DirEntry::Dir("nested"));
+    assert_eq!(nested.name(), "nested");
+    assert!(nested.file().is_none());
+
+    let index = entries
+        .iter()
+        .find(|entry| entry.name() == "index.html")
+        .unwrap();
+    assert_eq!(index.file(), StaticAssets::get("index.html"));
+
+    assert_eq!(
+        read_dir::<StaticAssets>("nested/"),
+        [DirEntry::Dir("nested/deep")]
+    );
+    assert_eq!(
+        read_dir::<StaticAssets>("/nested/deep"),
+        [DirEntry::File(
+            StaticAssets::get("nested/deep/file.txt").unwrap()
+        )]
+    );
+    assert_eq!(
+        read_dir::<StaticAssets>("nested/deep")[0].name(),
+        "file.txt"
+    );
+
+    assert!(read_dir::<StaticAssets>("missing").is_empty());
+    assert!(read_dir::<StaticAssets>("index.html").is_empty());
+    assert!(read_dir::<StaticAssets>("nest").is_empty());
+}
+
+#[test]
+fn test_walk() {
+    assert_eq!(
+        walk::<StaticAssets>(""),
+        StaticAssets::iter().collect::<Vec<_>>()
+    );
+    assert_eq!(walk::<StaticAssets>("nested"), ["nested/deep/file.txt"]);
+    assert_eq!(
+        walk::<StaticAssets>("/nested/deep/"),
+        ["nested/deep/file.txt"]
+    );
+    assert!(walk::<StaticAssets>("nest").is_empty());
+    assert!(walk::<StaticAssets>("missing").is_empty());
+
+    assert_eq!(
+        walk::<MergedAssets>("lib"),
+        [
+            "lib/v1/app.js",
+            "lib/v1/lib.copy.js",
+            "lib/v1/lib.js",
+            "lib/v1/vendor/lib.js"
+        ]
+    );
+
+    // Overlays yield their own files first, but walking still sorts by path
+    let mut paths = Overlay::<ThemeAssets, StaticAssets>::iter().collect::<Vec<_>>();
+    assert!(!paths.is_sorted());
+    paths.sort_unstable();
+    assert_eq!(walk::<Overlay<ThemeAssets, StaticAssets>>(""), paths);
+}
+
+#[test]
+fn test_exists_dir() {
+    for path in ["", "/", "nested", "nested/", "/nested/deep"] {
+        assert!(StaticAssets::exists_dir(path), "{path}");
+    }
+
+    for path in ["missing", "nest", "index.html", "nested/deep/file.txt"] {
+        assert!(!StaticAssets::exists_dir(path), "{path}");
+    }
+}
+
+#[test]
+fn test_derived_matches_default() {
+    let dirs = [
+        "",
+        "nested",
+        "nested/deep",
+        "lib",
+        "lib/v1",
+        "lib/v1/vendor",
+        "missing",
+    ];
+
+    for dir in dirs {
+        assert_eq!(
+            read_dir::<MergedAssets>(dir),
+            read_dir::<DefaultImpl<MergedAssets>>(dir),
+            "{dir}"
+        );
+        assert_eq!(
+            walk::<MergedAssets>(dir),
+            walk::<DefaultImpl<MergedAssets>>(dir),
+            "{dir}"
+        );
+        assert_eq!(
+            MergedAssets::exists_dir(dir),
+            DefaultImpl::<MergedAssets>::exists_dir(dir),
+            "{dir}"
+        );
+    }
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use axum::{Router, http::StatusCode};
+use axum_asset::{Asset, EtagStrategy, ServeOptions};
+
+use self::common::{get, get_header, get_status, get_with_headers, router_with};
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+struct StaticAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/static", etag_hash = "blake3")]
+struct Blake3Assets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/static", etag_hash = "xxh3")]
+struct Xxh3Assets;
+
+fn router_with_etag(etag: EtagStrategy) -> Router {
+    router_with::<StaticAssets>(ServeOptions::new().with_etag(etag))
+}
+
+fn custom_etag(hash: &str) -> String {
+    format!("\"v1-{}\"", &hash[..8])
+}
+
+async fn assert_etag(app: Router, expected: String) {
+    let response = get(app.clone(), "/static/index.html").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(get_header(&response, "etag"), Some(expected.clone()));
+
+    let cached_response = get_with_headers(
+        app,
+        "/static/index.html",
+        vec![("if-none-match", &expected)],
+    )
+    .await;
+    assert_eq!(get_status(&cached_response), StatusCode::NOT_MODIFIED);
+    assert_eq!(get_header(&cached_response, "etag"), Some(expected));
+}
+
+#[tokio::test]
+async fn test_etag_strategies() {
+    let hash = StaticAssets::get("index.html").unwrap().metadata.etag;
+
+    assert_etag(
+        router_with_etag(EtagStrategy::Strong),
+        format!("\"{hash}\""),
+    )
+    .await;
+    assert_etag(
+        router_with_etag(EtagStrategy::Weak),
+        format!("W/\"{hash}\""),
+    )
+    .await;
+    assert_etag(
+        router_with_etag(EtagStrategy::Truncated {
+            len: 16,
+            weak: false,
+        }),
+        format!("\"{}\"", &hash[..16]),
+    )
+    .await;
+    assert_etag(
+        router_with_etag(EtagStrategy::Truncated { len: 8, weak: true }),
+        format!("W/\"{}\"", &hash[..8]),
+    )
+    .await;
+    assert_etag(
+        router_with_etag(EtagStrategy::Custom(custom_etag)),
+        format!("\"v1-{}\"", &hash[..8]),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_etag_truncated_minimum_length() {
+    let hash = StaticAssets::get("index.html").unwrap().metadata.etag;
+    let min = EtagStrategy::MIN_TRUNCATED_LEN;
+
+    for len in [0, 1, min - 1] {
+        assert_etag(
+            router_with_etag(EtagStrategy::Truncated { len, weak: false }),
+            format!("\"{}\"", &hash[..min]),
+        )
+        .await;
+    }
+
+    // Longer than the hash keeps the whole hash
+    assert_eq!(
+        EtagStrategy::Truncated {
+            len: 1000,
+            weak: false
+        }
+        .header_value(hash),
+        format!("\"{hash}\"")
+    );
+}
+
+#[test]
+fn test_etag_hash() {
+    for path in StaticAssets::iter() {
+        let metadata = StaticAssets::get(path).unwrap().metadata;
+        assert_eq!(metadata.etag, metadata.content_hash);
+
+        let blake3 = Blake3Assets::get(path).unwrap().metadata;
+        assert_eq!(blake3.content_hash, metadata.content_hash);
+        assert_eq!(blake3.etag.len(), 64);
+        assert_ne!(blake3.etag, metadata.content_hash);
+
+        let xxh3 = Xxh3Assets::get(path).unwrap().metadata;
+        assert_eq!(xxh3.content_hash, metadata.content_hash);
+        assert_eq!(xxh3.etag.len(), 32);
+    }
+
+    // BLAKE3 of an empty input
+    assert_eq!(
+        Blake3Assets::get("empty.txt").unwrap().metadata.etag,
+        "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+    );
+}
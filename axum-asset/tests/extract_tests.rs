@@ -0,0 +1,88 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use axum_asset::{Asset, ExtractOptions, dump_assets_from};
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+struct StaticAssets;
+
+/// Create an empty directory unique to the test.
+fn extract_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+        .join("extract")
+        .join(name);
+
+    let _ = fs::remove_dir_all(&dir);
+
+    dir
+}
+
+fn assert_extracted(dir: &Path) {
+    for path in StaticAssets::iter() {
+        let file = StaticAssets::get(path).unwrap();
+        let target = dir.join(path);
+
+        assert_eq!(fs::read(&target).unwrap(), file.contents);
+        assert_eq!(
+            fs::metadata(&target).unwrap().modified().unwrap(),
+            UNIX_EPOCH + Duration::from_secs(file.metadata.last_modified)
+        );
+    }
+}
+
+#[test]
+fn test_extract_to() {
+    let dir = extract_dir("plain");
+    StaticAssets::extract_to(&dir).unwrap();
+
+    assert_extracted(&dir);
+    assert!(!dir.join("manifest.json").exists());
+}
+
+#[test]
+fn test_extract_with_manifest() {
+    let dir = extract_dir("manifest");
+    let options = ExtractOptions::new().with_manifest("meta/manifest.json");
+    StaticAssets::extract_with(&dir, &options).unwrap();
+
+    assert_extracted(&dir);
+
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&fs::read(dir.join("meta/manifest.json")).unwrap()).unwrap();
+    let files = manifest["files"].as_array().unwrap();
+    assert_eq!(files.len(), StaticAssets::len());
+
+    for (entry, path) in files.iter().zip(StaticAssets::iter()) {
+        let metadata = StaticAssets::get(path).unwrap().metadata;
+
+        assert_eq!(entry["path"], path);
+        assert_eq!(entry["size"], metadata.size);
+        assert_eq!(entry["mime_type"], metadata.mime_type);
+        assert_eq!(entry["content_hash"], metadata.content_hash);
+        assert_eq!(entry["etag"], metadata.etag);
+        assert_eq!(entry["integrity"], metadata.integrity);
+        assert_eq!(entry["last_modified"], metadata.last_modified);
+    }
+}
+
+#[test]
+fn test_dump_assets_from() {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    assert!(!dump_assets_from::<StaticAssets>(args(&["--port", "8080"])).unwrap());
+    assert!(dump_assets_from::<StaticAssets>(args(&["--dump-assets"])).is_err());
+
+    let dir = extract_dir("dump");
+    let dir_arg = format!("--dump-assets={}", dir.display());
+    assert!(
+        dump_assets_from::<StaticAssets>(args(&[&dir_arg, "--dump-manifest", "manifest.json"]))
+            .unwrap()
+    );
+
+    assert_extracted(&dir);
+    assert!(dir.join("manifest.json").exists());
+}
@@ -0,0 +1,80 @@
+use std::fs;
+
+use axum_asset::{Asset, Filter, Filtered, Overlay, Prefix, Prefixed};
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+struct StaticAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/theme")]
+struct ThemeAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/duplicates")]
+struct DuplicateAssets;
+
+struct V2;
+
+impl Prefix for V2 {
+    const PREFIX: &'static str = "v2";
+}
+
+struct Scripts;
+
+impl Filter for Scripts {
+    fn matches(path: &str) -> bool {
+        path.ends_with(".js")
+    }
+}
+
+/// Check that `files` and `total_size` agree with `iter` and `get`.
+fn assert_consistent<A: Asset>() {
+    let files: Vec<_> = A::files().collect();
+    let expected: Vec<_> = A::iter().map(|path| A::get(path).unwrap()).collect();
+
+    assert_eq!(files, expected);
+    assert_eq!(files.len(), A::len());
+    assert_eq!(
+        A::total_size(),
+        files.iter().map(|file| file.metadata.size).sum::<u64>()
+    );
+}
+
+#[test]
+fn test_files() {
+    let paths: Vec<_> = StaticAssets::files().map(|file| file.path).collect();
+    assert_eq!(paths, StaticAssets::iter().collect::<Vec<_>>());
+
+    for file in StaticAssets::files() {
+        let contents = fs::read(format!("tests/static/{}", file.path)).unwrap();
+        assert_eq!(file.contents, contents.as_slice(), "{}", file.path);
+    }
+}
+
+#[test]
+fn test_total_size() {
+    let size: u64 = StaticAssets::iter()
+        .map(|path| fs::metadata(format!("tests/static/{path}")).unwrap().len())
+        .sum();
+    assert_eq!(StaticAssets::total_size(), size);
+
+    // Deduplicated contents still count once per path
+    let size: u64 = DuplicateAssets::iter()
+        .map(|path| {
+            fs::metadata(format!("tests/duplicates/{path}"))
+                .unwrap()
+                .len()
+        })
+        .sum();
+    assert_eq!(DuplicateAssets::total_size(), size);
+}
+
+#[test]
+fn test_consistent_with_get() {
+    assert_consistent::<StaticAssets>();
+    assert_consistent::<DuplicateAssets>();
+    assert_consistent::<Overlay<ThemeAssets, StaticAssets>>();
+    assert_consistent::<Prefixed<StaticAssets, V2>>();
+    assert_consistent::<Filtered<StaticAssets, Scripts>>();
+}
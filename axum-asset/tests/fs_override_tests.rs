@@ -0,0 +1,190 @@
+mod common;
+
+use std::{fs, path::PathBuf, sync::Arc};
+
+use axum::http::StatusCode;
+use axum_asset::{Asset, AssetSource, FsOverride};
+use sha2::{Digest, Sha256};
+
+use self::common::{get, get_body, get_header, get_status, service_router};
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+struct StaticAssets;
+
+#[derive(Asset)]
+#[asset(
+    dir = "tests/static",
+    mime(path = "no-extension", type = "application/json")
+)]
+struct MimeOverrideAssets;
+
+/// Create an empty override directory unique to the test.
+fn override_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+        .join("fs-override")
+        .join(name);
+
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
+
+#[test]
+fn test_fallback() {
+    let source = FsOverride::new(override_dir("fallback"), StaticAssets);
+
+    for path in <StaticAssets as Asset>::iter() {
+        let file = source.get(path).unwrap();
+        assert_eq!(file.as_embedded(), <StaticAssets as Asset>::get(path));
+    }
+
+    assert_eq!(
+        source.iter().collect::<Vec<_>>(),
+        <StaticAssets as Asset>::iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_override() {
+    let dir = override_dir("override");
+    fs::write(dir.join("style.css"), "body { color: red; }\n").unwrap();
+    fs::create_dir_all(dir.join("legal")).unwrap();
+    fs::write(dir.join("legal/terms.txt"), "Terms\n").unwrap();
+
+    let source = FsOverride::new(&dir, StaticAssets);
+
+    let file = source.get("style.css").unwrap();
+    assert!(file.as_embedded().is_none());
+    assert_eq!(file.path(), "style.css");
+    assert_eq!(file.contents(), b"body { color: red; }\n");
+    assert_eq!(file.size(), 21);
+    assert_eq!(file.mime_type(), "text/css");
+    assert_eq!(file.charset(), Some("utf-8"));
+    assert_eq!(
+        file.content_hash(),
+        hex::encode(Sha256::digest(file.contents()))
+    );
+    assert_eq!(file.etag(), file.content_hash());
+    assert!(file.last_modified() > 0);
+
+    assert_eq!(
+        source.get("legal/terms.txt").unwrap().contents(),
+        b"Terms\n"
+    );
+
+    let paths: Vec<_> = source.iter().collect();
+    assert!(paths.iter().any(|path| path == "legal/terms.txt"));
+    assert_eq!(paths.len(), <StaticAssets as Asset>::len() + 1);
+}
+
+#[test]
+fn test_override_keeps_mime_type() {
+    let dir = override_dir("mime-type");
+    fs::write(dir.join("no-extension"), "{\"patched\":true}").unwrap();
+    fs::write(dir.join("extra"), "{}").unwrap();
+
+    let source = FsOverride::new(&dir, MimeOverrideAssets);
+
+    let file = source.get("no-extension").unwrap();
+    assert!(file.as_embedded().is_none());
+    assert_eq!(file.mime_type(), "application/json");
+    assert_eq!(file.charset(), Some("utf-8"));
+
+    // Files without an embedded counterpart are guessed from their extension
+    assert_eq!(
+        source.get("extra").unwrap().mime_type(),
+        "application/octet-stream"
+    );
+}
+
+#[test]
+fn test_reload_on_change() {
+    let dir = override_dir("reload");
+    let source = FsOverride::new(&dir, StaticAssets);
+
+    fs::write(dir.join("index.html"), "<p>One</p>").unwrap();
+    assert_eq!(source.get("index.html").unwrap().contents(), b"<p>One</p>");
+
+    fs::write(dir.join("index.html"), "<p>Two!</p>").unwrap();
+    assert_eq!(source.get("index.html").unwrap().contents(), b"<p>Two!</p>");
+
+    fs::remove_file(dir.join("index.html")).unwrap();
+    assert_eq!(
+        source.get("index.html").unwrap().as_embedded(),
+        <StaticAssets as Asset>::get("index.html")
+    );
+}
+
+#[test]
+fn test_path_traversal() {
+    let dir = override_dir("traversal/root");
+    fs::write(dir.parent().unwrap().join("secret.txt"), "secret").unwrap();
+
+    let source = FsOverride::new(&dir, StaticAssets);
+
+    assert!(source.get("../secret.txt").is_none());
+    assert!(source.get("/etc/passwd").is_none());
+    assert!(source.get("nested/../../secret.txt").is_none());
+}
+
+#[tokio::test]
+async fn test_service() {
+    let dir = override_dir("service");
+    fs::write(dir.join("data.json"), "{\"patched\": true}").unwrap();
+
+    let app = service_router(Arc::new(FsOverride::new(&dir, StaticAssets)));
+
+    let response = get(app.clone(), "/static/data.json").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(
+        get_header(&response, "etag").unwrap(),
+        format!(
+            "\"{}\"",
+            hex::encode(Sha256::digest(b"{\"patched\": true}"))
+        )
+    );
+    assert_eq!(get_body(response).await, "{\"patched\": true}");
+
+    let response = get(app, "/static/index.html").await;
+    assert_eq!(
+        get_body(response).await,
+        <StaticAssets as Asset>::get("index.html").unwrap().contents
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_outside_dir() {
+    use std::os::unix::fs::symlink;
+
+    let outside = override_dir("symlink-outside");
+    fs::write(outside.join("secret.txt"), "secret\n").unwrap();
+    fs::write(outside.join("index.html"), "<p>Outside</p>").unwrap();
+
+    let dir = override_dir("symlink");
+    fs::write(dir.join("inside.txt"), "inside\n").unwrap();
+    symlink(outside.join("secret.txt"), dir.join("secret.txt")).unwrap();
+    symlink(outside.join("index.html"), dir.join("index.html")).unwrap();
+    symlink(&outside, dir.join("linked")).unwrap();
+    symlink(dir.join("inside.txt"), dir.join("alias.txt")).unwrap();
+
+    let source = FsOverride::new(&dir, StaticAssets);
+
+    // Symlinks escaping the override directory are ignored
+    assert!(source.get("secret.txt").is_none());
+    assert!(source.get("linked/secret.txt").is_none());
+    assert_eq!(
+        source.get("index.html").unwrap().as_embedded(),
+        <StaticAssets as Asset>::get("index.html")
+    );
+
+    // Symlinks staying inside it are followed
+    assert_eq!(source.get("alias.txt").unwrap().contents(), b"inside\n");
+
+    let paths: Vec<_> = source.iter().collect();
+    assert!(paths.iter().any(|path| path == "alias.txt"));
+    assert!(!paths.iter().any(|path| path == "secret.txt"));
+    assert!(!paths.iter().any(|path| path.starts_with("linked/")));
+}
@@ -0,0 +1,46 @@
+use axum_asset::Asset;
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+#[asset(dir = "tests/theme", prefix = "theme")]
+#[asset(dir = "tests/duplicates", prefix = "theme/vendor")]
+struct SiteAssets;
+
+fn glob(pattern: &str) -> Vec<&'static str> {
+    SiteAssets::glob(pattern)
+        .unwrap()
+        .map(|file| file.path)
+        .collect()
+}
+
+#[test]
+fn test_glob() {
+    assert_eq!(glob("**/*.css"), ["style.css", "theme/style.css"]);
+    assert_eq!(glob("*.css"), ["style.css"]);
+    assert_eq!(glob("theme/*.css"), ["theme/style.css"]);
+    assert_eq!(glob("theme/*"), ["theme/logo.svg", "theme/style.css"]);
+    assert_eq!(
+        glob("theme/**/lib*.js"),
+        [
+            "theme/vendor/lib.copy.js",
+            "theme/vendor/lib.js",
+            "theme/vendor/vendor/lib.js"
+        ]
+    );
+    assert_eq!(glob("*.{html,json}"), ["data.json", "index.html"]);
+    assert_eq!(glob("nested/**"), ["nested/deep/file.txt"]);
+    assert_eq!(glob("**"), SiteAssets::iter().collect::<Vec<_>>());
+    assert!(glob("*.scss").is_empty());
+}
+
+#[test]
+fn test_glob_returns_files() {
+    let file = SiteAssets::glob("theme/style.css").unwrap().next().unwrap();
+    assert_eq!(Some(file), SiteAssets::get("theme/style.css"));
+}
+
+#[test]
+fn test_invalid_glob() {
+    let error = SiteAssets::glob("theme/[a-").err().unwrap();
+    assert!(!error.to_string().is_empty());
+}
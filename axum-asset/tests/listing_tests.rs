@@ -0,0 +1,191 @@
+mod common;
+
+use axum::{Router, http::StatusCode};
+use axum_asset::{Asset, ServeOptions};
+
+use self::common::{get, get_body, get_header, get_status, get_with_headers, router, router_with};
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+struct StaticAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/special", prefix = "odd dir")]
+struct SpecialAssets;
+
+#[derive(Asset)]
+#[asset(archive = "tests/archive/colons.tar")]
+struct ColonAssets;
+
+fn listing_router() -> Router {
+    router_with::<StaticAssets>(ServeOptions::new().with_directory_listing())
+}
+
+async fn get_json(app: Router, uri: &str) -> serde_json::Value {
+    let response = get_with_headers(app, uri, vec![("accept", "application/json")]).await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(
+        get_header(&response, "content-type").unwrap(),
+        "application/json"
+    );
+
+    serde_json::from_slice(&get_body(response).await).unwrap()
+}
+
+#[tokio::test]
+async fn test_listing_disabled_by_default() {
+    let app = router::<StaticAssets>();
+
+    for uri in ["/static", "/static/nested", "/static/nested/"] {
+        let response = get(app.clone(), uri).await;
+        assert_eq!(get_status(&response), StatusCode::NOT_FOUND);
+    }
+}
+
+#[tokio::test]
+async fn test_html_listing() {
+    let app = listing_router();
+
+    let response = get(app.clone(), "/static").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(
+        get_header(&response, "content-type").unwrap(),
+        "text/html; charset=utf-8"
+    );
+    assert_eq!(get_header(&response, "vary").unwrap(), "accept");
+
+    let html = String::from_utf8(get_body(response).await.to_vec()).unwrap();
+    assert!(html.contains("<title>Index of /</title>"));
+    assert!(html.contains("<a href=\"./static/nested/\">nested/</a>"));
+    assert!(html.contains("<a href=\"./static/index.html\">index.html</a>"));
+    assert!(html.contains("text/html"));
+    assert!(!html.contains("../"));
+
+    // Subdirectories come before files
+    assert!(html.find("nested/").unwrap() < html.find("data.json").unwrap());
+
+    let response = get(app.clone(), "/static/nested/deep/").await;
+    let html = String::from_utf8(get_body(response).await.to_vec()).unwrap();
+    assert!(html.contains("<title>Index of /nested/deep</title>"));
+    assert!(html.contains("<a href=\"./../\">../</a>"));
+    assert!(html.contains("<a href=\"./file.txt\">file.txt</a>"));
+
+    let response = get(app, "/static/nested/deep").await;
+    let html = String::from_utf8(get_body(response).await.to_vec()).unwrap();
+    assert!(html.contains("<a href=\"./deep/file.txt\">file.txt</a>"));
+}
+
+#[tokio::test]
+async fn test_json_listing() {
+    let app = listing_router();
+
+    let listing = get_json(app.clone(), "/static").await;
+    assert_eq!(listing["path"], "");
+
+    let entries = listing["entries"].as_array().unwrap();
+    assert_eq!(entries[0]["name"], "nested");
+    assert_eq!(entries[0]["type"], "directory");
+    assert_eq!(entries.len(), StaticAssets::len());
+
+    let index = entries
+        .iter()
+        .find(|entry| entry["name"] == "index.html")
+        .unwrap();
+    let metadata = StaticAssets::get("index.html").unwrap().metadata;
+    assert_eq!(index["type"], "file");
+    assert_eq!(index["path"], "index.html");
+    assert_eq!(index["size"], metadata.size);
+    assert_eq!(index["mime_type"], metadata.mime_type);
+    assert_eq!(index["last_modified"], metadata.last_modified);
+
+    let listing = get_json(app.clone(), "/static/nested/").await;
+    assert_eq!(listing["path"], "nested");
+    assert_eq!(
+        listing["entries"],
+        serde_json::json!([{ "name": "deep", "type": "directory" }])
+    );
+}
+
+#[tokio::test]
+async fn test_files_still_served() {
+    let app = listing_router();
+
+    let response = get(app.clone(), "/static/nested/deep/file.txt").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+
+    let response = get(app, "/static/missing/").await;
+    assert_eq!(get_status(&response), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_links_are_percent_encoded() {
+    let app = router_with::<SpecialAssets>(ServeOptions::new().with_directory_listing());
+
+    let response = get(app.clone(), "/static/odd%20dir/").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+
+    let html = String::from_utf8(get_body(response).await.to_vec()).unwrap();
+    assert!(html.contains("<a href=\"./100%25.txt\">100%.txt</a>"));
+    assert!(html.contains("<a href=\"./a%23b.txt\">a#b.txt</a>"));
+    assert!(html.contains("<a href=\"./my%20file.txt\">my file.txt</a>"));
+
+    let html =
+        String::from_utf8(get_body(get(app.clone(), "/static").await).await.to_vec()).unwrap();
+    assert!(html.contains("<a href=\"./static/odd%20dir/\">odd dir/</a>"));
+
+    // The links resolve to the files
+    for (uri, body) in [
+        ("/static/odd%20dir/100%25.txt", "percent\n"),
+        ("/static/odd%20dir/a%23b.txt", "hash\n"),
+        ("/static/odd%20dir/my%20file.txt", "space\n"),
+    ] {
+        let response = get(app.clone(), uri).await;
+        assert_eq!(get_status(&response), StatusCode::OK, "{uri}");
+        assert_eq!(get_body(response).await, body);
+    }
+}
+
+#[tokio::test]
+async fn test_links_with_colons_stay_relative() {
+    let app = router_with::<ColonAssets>(ServeOptions::new().with_directory_listing());
+
+    let response = get(app.clone(), "/static/sub/").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+
+    let html = String::from_utf8(get_body(response).await.to_vec()).unwrap();
+    assert!(html.contains("<a href=\"./javascript:alert(1)\">javascript:alert(1)</a>"));
+    assert!(html.contains("<a href=\"./report:v1.txt\">report:v1.txt</a>"));
+
+    // Without a trailing slash, the directory name leads the links
+    let response = get(app.clone(), "/static/javascript:x").await;
+    let html = String::from_utf8(get_body(response).await.to_vec()).unwrap();
+    assert!(html.contains("<a href=\"./javascript:x/../\">../</a>"));
+    assert!(html.contains("<a href=\"./javascript:x/file.txt\">file.txt</a>"));
+
+    let response = get(app, "/static/sub/report:v1.txt").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(get_body(response).await, "report\n");
+}
+
+#[tokio::test]
+async fn test_accept_quality_values() {
+    let cases = [
+        ("application/json", true),
+        ("application/json, text/plain, */*", true),
+        ("text/html;q=0.5, application/json", true),
+        ("application/json;q=0", false),
+        ("application/json; q=0.0, */*", false),
+        ("text/html, application/json;q=0.9", false),
+        ("text/*;q=0.8, */*;q=0.1, application/json;q=0.5", false),
+        ("*/*", false),
+        ("text/html", false),
+    ];
+
+    for (accept, json) in cases {
+        let response =
+            get_with_headers(listing_router(), "/static", vec![("accept", accept)]).await;
+        let content_type = get_header(&response, "content-type").unwrap();
+
+        assert_eq!(content_type == "application/json", json, "{accept}");
+    }
+}
@@ -0,0 +1,200 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::{Router, http::StatusCode, response::IntoResponse, routing::get as route_get};
+use axum_asset::{Asset, Conditional, EmbeddedFile, EtagStrategy, ServeOptions};
+
+use self::common::{
+    get, get_body, get_header, get_status, get_with_headers, router, service_router,
+};
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+struct StaticAssets;
+
+fn index() -> EmbeddedFile {
+    <StaticAssets as Asset>::get("index.html").unwrap()
+}
+
+fn handler_router() -> Router {
+    Router::new()
+        .route("/", route_get(|| async { index() }))
+        .route(
+            "/conditional",
+            route_get(|conditional: Conditional| async move { index().respond_to(&conditional) }),
+        )
+        .route(
+            "/weak",
+            route_get(|conditional: Conditional| async move {
+                let options = ServeOptions::new().with_etag(EtagStrategy::Weak);
+                index().respond_with(&conditional, &options)
+            }),
+        )
+}
+
+#[tokio::test]
+async fn test_into_response() {
+    let file = index();
+    let response = get(handler_router(), "/").await;
+
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(
+        get_header(&response, "etag"),
+        Some(format!("\"{}\"", file.metadata.etag))
+    );
+    assert_eq!(
+        get_header(&response, "content-type").as_deref(),
+        Some("text/html; charset=utf-8")
+    );
+    assert_eq!(get_body(response).await, file.contents);
+
+    // Plain `IntoResponse` ignores conditional headers
+    let response = file.into_response();
+    assert_eq!(get_status(&response), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_respond_to_conditional() {
+    let file = index();
+    let etag = format!("\"{}\"", file.metadata.etag);
+
+    let response = get_with_headers(
+        handler_router(),
+        "/conditional",
+        vec![("if-none-match", &etag)],
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::NOT_MODIFIED);
+    assert_eq!(get_header(&response, "etag"), Some(etag.clone()));
+
+    let response = get_with_headers(
+        handler_router(),
+        "/conditional",
+        vec![("if-none-match", "\"stale\"")],
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+
+    let last_modified = get_header(&response, "last-modified").unwrap();
+    let response = get_with_headers(
+        handler_router(),
+        "/conditional",
+        vec![("if-modified-since", &last_modified)],
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::NOT_MODIFIED);
+
+    let response = get_with_headers(handler_router(), "/weak", vec![]).await;
+    assert_eq!(get_header(&response, "etag"), Some(format!("W/{etag}")));
+}
+
+#[tokio::test]
+async fn test_range() {
+    let contents = index().contents;
+
+    let response = get_with_headers(
+        router::<StaticAssets>(),
+        "/static/index.html",
+        vec![("range", "bytes=0-4")],
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(
+        get_header(&response, "content-range"),
+        Some(format!("bytes 0-4/{}", contents.len()))
+    );
+    assert_eq!(
+        get_header(&response, "content-length").as_deref(),
+        Some("5")
+    );
+    assert_eq!(get_body(response).await, &contents[..5]);
+
+    // Suffix ranges select the last bytes
+    let response = get_with_headers(
+        router::<StaticAssets>(),
+        "/static/index.html",
+        vec![("range", "bytes=-3")],
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(get_body(response).await, &contents[contents.len() - 3..]);
+
+    // Open-ended ranges past the end are clamped
+    let range = format!("bytes=10-{}", contents.len() * 2);
+    let response = get_with_headers(
+        router::<StaticAssets>(),
+        "/static/index.html",
+        vec![("range", &range)],
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(get_body(response).await, &contents[10..]);
+
+    let range = format!("bytes={}-", contents.len());
+    let response = get_with_headers(
+        router::<StaticAssets>(),
+        "/static/index.html",
+        vec![("range", &range)],
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(
+        get_header(&response, "content-range"),
+        Some(format!("bytes */{}", contents.len()))
+    );
+
+    // Multiple ranges are answered with the whole file
+    let response = get_with_headers(
+        router::<StaticAssets>(),
+        "/static/index.html",
+        vec![("range", "bytes=0-1,4-5")],
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(
+        get_header(&response, "accept-ranges").as_deref(),
+        Some("bytes")
+    );
+    assert_eq!(get_body(response).await, contents);
+}
+
+#[tokio::test]
+async fn test_if_range() {
+    let file = index();
+    let etag = format!("\"{}\"", file.metadata.etag);
+
+    let response = get_with_headers(
+        handler_router(),
+        "/conditional",
+        vec![("range", "bytes=0-4"), ("if-range", &etag)],
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::PARTIAL_CONTENT);
+
+    let response = get_with_headers(
+        handler_router(),
+        "/conditional",
+        vec![("range", "bytes=0-4"), ("if-range", "\"stale\"")],
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(get_body(response).await, file.contents);
+}
+
+#[tokio::test]
+async fn test_service_range() {
+    let contents = index().contents;
+
+    let response = get_with_headers(
+        service_router(Arc::new(StaticAssets)),
+        "/static/index.html",
+        vec![("range", "bytes=1-2")],
+    )
+    .await;
+    assert_eq!(get_status(&response), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(get_body(response).await, &contents[1..3]);
+
+    let response = get(service_router(Arc::new(StaticAssets)), "/static/index.html").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+}
@@ -3,12 +3,29 @@ mod common;
 use axum::http::StatusCode;
 use axum_asset::Asset;
 
-use self::common::{get, get_body, get_header, get_status, router};
+use self::common::{content_type, get, get_body, get_header, get_status, router};
 
 #[derive(Asset)]
 #[asset(dir = "tests/static")]
 struct StaticAssets;
 
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+#[asset(dir = "tests/duplicates", prefix = "vendor")]
+struct MergedAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/static", strip = "nested", prefix = "v2")]
+struct RemappedAssets;
+
+#[derive(Asset)]
+#[asset(
+    dir = "tests/static",
+    mime(path = "index.html", type = "text/html; charset=iso-8859-1"),
+    mime(path = "style.css", type = "text/css;CHARSET=utf-8")
+)]
+struct CharsetAssets;
+
 async fn test_file(path: &str) {
     let app = router::<StaticAssets>();
 
@@ -23,8 +40,8 @@ async fn test_file(path: &str) {
         Some(format!("\"{}\"", file.metadata.content_hash))
     );
     assert_eq!(
-        get_header(&response, "content-type").as_deref(),
-        Some(file.metadata.mime_type)
+        get_header(&response, "content-type"),
+        Some(content_type(file))
     );
     assert!(get_header(&response, "last-modified").is_some());
     assert_eq!(
@@ -56,3 +73,66 @@ async fn test_all_files() {
         test_file(file).await;
     }
 }
+
+#[tokio::test]
+async fn test_charset() {
+    let app = router::<StaticAssets>();
+
+    let response = get(app.clone(), "/static/index.html").await;
+    assert_eq!(
+        get_header(&response, "content-type").as_deref(),
+        Some("text/html; charset=utf-8")
+    );
+
+    let response = get(app, "/static/no-extension").await;
+    assert_eq!(
+        get_header(&response, "content-type").as_deref(),
+        Some("application/octet-stream")
+    );
+}
+
+#[tokio::test]
+async fn test_charset_from_mime_type() {
+    let app = router::<CharsetAssets>();
+
+    let response = get(app.clone(), "/static/index.html").await;
+    assert_eq!(
+        get_header(&response, "content-type").as_deref(),
+        Some("text/html; charset=iso-8859-1")
+    );
+
+    let response = get(app, "/static/style.css").await;
+    assert_eq!(
+        get_header(&response, "content-type").as_deref(),
+        Some("text/css;charset=utf-8")
+    );
+}
+
+#[tokio::test]
+async fn test_merged_directories() {
+    let app = router::<MergedAssets>();
+
+    let response = get(app.clone(), "/static/index.html").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+
+    let response = get(app.clone(), "/static/vendor/vendor/lib.js").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(
+        get_body(response).await,
+        MergedAssets::get("vendor/vendor/lib.js").unwrap().contents
+    );
+
+    let response = get(app, "/static/lib.js").await;
+    assert_eq!(get_status(&response), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_remapped_paths() {
+    let app = router::<RemappedAssets>();
+
+    let response = get(app.clone(), "/static/v2/deep/file.txt").await;
+    assert_eq!(get_status(&response), StatusCode::OK);
+
+    let response = get(app, "/static/nested/deep/file.txt").await;
+    assert_eq!(get_status(&response), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Method, Request, StatusCode},
+};
+use axum_asset::{Asset, AssetRef, AssetService, Overlay, ServeOptions};
+use tower::ServiceExt;
+
+use self::common::{
+    content_type, get, get_body, get_header, get_status, get_with_headers, service_router,
+};
+
+#[derive(Asset)]
+#[asset(dir = "tests/static")]
+struct StaticAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/theme")]
+struct ThemeAssets;
+
+#[derive(Asset)]
+#[asset(dir = "tests/special")]
+struct SpecialAssets;
+
+fn sources() -> Vec<Arc<dyn axum_asset::AssetSource>> {
+    vec![
+        Arc::new(StaticAssets),
+        Arc::new(Overlay::<ThemeAssets, StaticAssets>::default()),
+    ]
+}
+
+#[test]
+fn test_asset_source() {
+    let source: Arc<dyn axum_asset::AssetSource> = Arc::new(StaticAssets);
+
+    let paths: Vec<_> = source.iter().collect();
+    assert_eq!(paths, StaticAssets::iter().collect::<Vec<_>>());
+
+    for path in StaticAssets::iter() {
+        let file = StaticAssets::get(path).unwrap();
+        let asset = source.get(path).unwrap();
+
+        assert_eq!(asset, AssetRef::from(file));
+        assert_eq!(asset.as_embedded(), Some(file));
+        assert_eq!(asset.path(), file.path);
+        assert_eq!(asset.contents(), file.contents);
+        assert_eq!(asset.content_hash(), file.metadata.content_hash);
+        assert_eq!(asset.content_digest(), file.metadata.content_digest);
+        assert_eq!(asset.etag(), file.metadata.etag);
+        assert_eq!(asset.last_modified(), file.metadata.last_modified);
+        assert_eq!(asset.mime_type(), file.metadata.mime_type);
+        assert_eq!(asset.charset(), file.metadata.charset);
+        assert_eq!(asset.size(), file.metadata.size);
+    }
+
+    assert!(source.get("missing.txt").is_none());
+}
+
+#[tokio::test]
+async fn test_service() {
+    for source in sources() {
+        let app = service_router(source.clone());
+
+        for path in source.iter() {
+            let file = source.get(&path).unwrap().as_embedded().unwrap();
+
+            let response = get(app.clone(), &format!("/static/{path}")).await;
+            assert_eq!(get_status(&response), StatusCode::OK);
+            assert_eq!(
+                get_header(&response, "content-type").unwrap(),
+                content_type(file)
+            );
+            assert_eq!(get_body(response).await, file.contents);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_service_selects_source_at_runtime() {
+    let app = service_router(sources().pop().unwrap());
+
+    let response = get(app, "/static/style.css").await;
+    assert_eq!(
+        get_body(response).await,
+        ThemeAssets::get("style.css").unwrap().contents
+    );
+}
+
+#[tokio::test]
+async fn test_service_conditional_request() {
+    let app = service_router(Arc::new(StaticAssets));
+
+    let response = get(app.clone(), "/static/index.html").await;
+    let etag = get_header(&response, "etag").unwrap();
+
+    let response =
+        get_with_headers(app, "/static/index.html", vec![("if-none-match", &etag)]).await;
+    assert_eq!(get_status(&response), StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn test_service_not_found() {
+    let app = service_router(Arc::new(StaticAssets));
+
+    let response = get(app, "/static/missing.txt").await;
+    assert_eq!(get_status(&response), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_service_ignores_directory_listing() {
+    let service = AssetService::new(Arc::new(StaticAssets))
+        .with_options(ServeOptions::new().with_directory_listing());
+    let app = Router::new().nest_service("/static", service);
+
+    for uri in ["/static/nested", "/static/nested/"] {
+        let response = get(app.clone(), uri).await;
+        assert_eq!(get_status(&response), StatusCode::NOT_FOUND, "{uri}");
+    }
+}
+
+#[tokio::test]
+async fn test_service_decodes_path() {
+    let app = service_router(Arc::new(SpecialAssets));
+
+    for (uri, body) in [
+        ("/static/my%20file.txt", "space\n"),
+        ("/static/100%25.txt", "percent\n"),
+        ("/static/a%23b.txt", "hash\n"),
+    ] {
+        let response = get(app.clone(), uri).await;
+        assert_eq!(get_status(&response), StatusCode::OK, "{uri}");
+        assert_eq!(get_body(response).await, body.as_bytes());
+    }
+
+    for uri in ["/static/100%.txt", "/static/my%2", "/static/%FF.txt"] {
+        let response = get(app.clone(), uri).await;
+        assert_eq!(get_status(&response), StatusCode::NOT_FOUND, "{uri}");
+    }
+}
+
+#[tokio::test]
+async fn test_service_methods() {
+    let app = service_router(Arc::new(StaticAssets));
+
+    let request = |method| {
+        Request::builder()
+            .method(method)
+            .uri("/static/index.html")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(request(Method::HEAD)).await.unwrap();
+    assert_eq!(get_status(&response), StatusCode::OK);
+    assert_eq!(
+        get_header(&response, "content-length").unwrap(),
+        StaticAssets::get("index.html")
+            .unwrap()
+            .metadata
+            .size
+            .to_string()
+    );
+    assert!(get_body(response).await.is_empty());
+
+    let response = app.oneshot(request(Method::POST)).await.unwrap();
+    assert_eq!(get_status(&response), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(get_header(&response, "allow").unwrap(), "GET, HEAD");
+}
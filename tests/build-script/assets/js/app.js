@@ -0,0 +1 @@
+console.log('__VERSION__');
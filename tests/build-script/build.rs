@@ -0,0 +1,17 @@
+use axum_asset_build::AssetBuilder;
+
+fn main() -> std::io::Result<()> {
+    // The `$` checks that the emitted module escapes it for the derive
+    AssetBuilder::new("assets")
+        .transform(|path, contents| {
+            (path.extension().is_none_or(|ext| ext != "map")).then_some(contents)
+        })
+        .transform(|_, contents| {
+            let contents = String::from_utf8(contents).ok()?;
+            Some(contents.replace("__VERSION__", "1.2.3").into_bytes())
+        })
+        .stage("dist$1")?
+        .emit_module("assets.rs", "BuiltAssets")?;
+
+    Ok(())
+}
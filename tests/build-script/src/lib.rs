@@ -0,0 +1,3 @@
+//! Embeds the assets staged by `build.rs`.
+
+include!(concat!(env!("OUT_DIR"), "/assets.rs"));
@@ -0,0 +1,32 @@
+use std::fs;
+
+use axum_asset::Asset;
+use axum_asset_build_script_test::BuiltAssets;
+
+#[test]
+fn test_staged_assets() {
+    assert_eq!(
+        BuiltAssets::iter().collect::<Vec<_>>(),
+        ["index.html", "js/app.js"]
+    );
+
+    let file = BuiltAssets::get("js/app.js").unwrap();
+    assert_eq!(file.contents, b"console.log('1.2.3');\n");
+    assert_eq!(file.metadata.mime_type, "text/javascript");
+
+    // Modification times come from the source files, not the build
+    let modified = fs::metadata("assets/index.html")
+        .unwrap()
+        .modified()
+        .unwrap()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    assert_eq!(
+        BuiltAssets::get("index.html")
+            .unwrap()
+            .metadata
+            .last_modified,
+        modified
+    );
+}